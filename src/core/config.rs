@@ -7,6 +7,8 @@ pub struct GameConfig {
     pub player_base_speed: f32,
     pub player_base_health: i32,
     pub player_base_damage: i32,
+    #[serde(default = "default_auto_cast")]
+    pub auto_cast_abilities: bool, // When off, abilities wait for their Q/E/R key
     
     // Enemy settings
     pub enemy_spawn_rate: f32,
@@ -39,6 +41,7 @@ impl Default for GameConfig {
             player_base_speed: 200.0,
             player_base_health: 100,
             player_base_damage: 10,
+            auto_cast_abilities: true,
             enemy_spawn_rate: 3.0,
             enemy_difficulty_scaling: 1.1,
             boss_spawn_time: 600.0,
@@ -57,6 +60,10 @@ impl Default for GameConfig {
     }
 }
 
+fn default_auto_cast() -> bool {
+    true
+}
+
 impl GameConfig {
     pub fn load() -> Self {
         // Try to load from file, otherwise use defaults
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::game::enemy::EnemyType;
 
 #[derive(Event)]
 pub struct GameEvent {
@@ -11,6 +12,8 @@ pub struct PlayerEvent {
     pub event_type: PlayerEventType,
 }
 
+/// A request to damage `target`, resolved by `combat::damage::process_damage_events`.
+/// Environmental damage uses `Entity::PLACEHOLDER` as the attacker.
 #[derive(Event)]
 pub struct CombatEvent {
     pub attacker: Entity,
@@ -18,12 +21,35 @@ pub struct CombatEvent {
     pub damage: i32,
     pub damage_type: DamageType,
     pub position: Vec3,
+    pub kind: HitKind,
+}
+
+/// How a hit was delivered. Contact ticks skip the player's damage bonuses and crits,
+/// and only direct hits roll on-hit effects.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HitKind {
+    Direct,
+    Contact,   // The player's continuous damage while touching an enemy
+    Tick,      // Status damage over time and area effect ticks
+    Reflected, // Thorns
+}
+
+/// Damage that actually landed after armor, crits and resistances
+#[derive(Event)]
+pub struct DamageDealtEvent {
+    pub attacker: Entity,
+    pub target: Entity,
+    pub amount: i32,
+    pub damage_type: DamageType,
+    pub critical: bool,
+    pub position: Vec3,
+    pub kind: HitKind,
 }
 
 #[derive(Clone, Copy)]
 pub enum GameEventType {
     WaveCompleted,
-    BossDefeated,
+    BossDefeated(EnemyType),
     PlayerLevelUp,
 }
 
@@ -34,7 +60,15 @@ pub enum PlayerEventType {
     Died,
 }
 
-#[derive(Clone, Copy)]
+/// Health restored to `target`, for popups
+#[derive(Event)]
+pub struct HealEvent {
+    pub target: Entity,
+    pub amount: i32,
+    pub position: Vec3,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum DamageType {
     Physical,
     Magic,
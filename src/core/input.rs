@@ -20,9 +20,24 @@ pub enum Action {
     Attack,
     Dash,
     UseAbility(u8),
+    ToggleLock(u8),
     Interact,
 }
 
+impl InputBuffer {
+    /// Takes the oldest buffered `action` pressed within `buffer_time`, if any
+    pub fn consume(&mut self, action: Action, now: f32) -> bool {
+        let buffer_time = self.buffer_time;
+        let found = self.buffer.iter().position(|input| {
+            input.action == action && now - input.timestamp < buffer_time
+        });
+        if let Some(index) = found {
+            self.buffer.remove(index);
+        }
+        found.is_some()
+    }
+}
+
 impl Default for InputBuffer {
     fn default() -> Self {
         Self {
@@ -38,7 +53,7 @@ pub fn buffer_input_system(
     mut buffer: ResMut<InputBuffer>,
     time: Res<Time>,
 ) {
-    let current_time = time.elapsed_seconds();
+    let current_time = time.elapsed_secs();
     
     // Clean old inputs
     let buffer_time = buffer.buffer_time;
@@ -74,8 +89,39 @@ pub fn buffer_input_system(
         });
     }
     
+    // Ability slots: Q head, E torso, R legs, G ultimate (T opens the talent menu)
+    for (slot, key) in [KeyCode::KeyQ, KeyCode::KeyE, KeyCode::KeyR, KeyCode::KeyG].into_iter().enumerate() {
+        if keys.just_pressed(key) {
+            buffer.buffer.push_back(InputAction {
+                action: Action::UseAbility(slot as u8),
+                timestamp: current_time,
+            });
+        }
+    }
+    
+    // Fruit slot locks: Z head, X torso, C legs
+    for (slot, key) in [KeyCode::KeyZ, KeyCode::KeyX, KeyCode::KeyC].into_iter().enumerate() {
+        if keys.just_pressed(key) {
+            buffer.buffer.push_back(InputAction {
+                action: Action::ToggleLock(slot as u8),
+                timestamp: current_time,
+            });
+        }
+    }
+    
+    if keys.just_pressed(KeyCode::KeyF) {
+        buffer.buffer.push_back(InputAction {
+            action: Action::Interact,
+            timestamp: current_time,
+        });
+    }
+    
+    // Movement is pushed every frame, so drop that first to keep presses alive for their whole window
     while buffer.buffer.len() > buffer.max_size {
-        buffer.buffer.pop_front();
+        match buffer.buffer.iter().position(|input| matches!(input.action, Action::Move(_))) {
+            Some(index) => { buffer.buffer.remove(index); }
+            None => { buffer.buffer.pop_front(); }
+        }
     }
 }
 
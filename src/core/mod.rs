@@ -24,6 +24,8 @@ impl Plugin for CorePlugin {
             .add_event::<events::GameEvent>()
             .add_event::<events::PlayerEvent>()
             .add_event::<events::CombatEvent>()
+            .add_event::<events::DamageDealtEvent>()
+            .add_event::<events::HealEvent>()
             // Systems
             .add_systems(Startup, (
                 camera::setup_camera,
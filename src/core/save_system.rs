@@ -23,6 +23,8 @@ pub struct SaveData {
     pub prestige_points: u32,
     pub total_combo: u32,
     pub max_combo: u32,
+    #[serde(default)]
+    pub discovered_synergies: Vec<String>, // Ids from the fruit SynergyTable
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -29,6 +29,7 @@ pub struct GameStats {
     pub current_level: usize,
     pub score: u32,
     pub enemies_killed: u32,
+    pub elites_killed: u32,
     pub time_played: f32,
     pub coins_collected: u32,
     pub damage_dealt: u32,
@@ -43,6 +44,7 @@ impl Default for GameStats {
             current_level: 1,
             score: 0,
             enemies_killed: 0,
+            elites_killed: 0,
             time_played: 0.0,
             coins_collected: 0,
             damage_dealt: 0,
@@ -16,6 +16,8 @@ pub struct PowerUpSlots {
 pub struct FruitSlot {
     pub fruit_type: u8,           // 0-6 for different fruits (visual)
     pub powerup: PowerUpType,     // The actual power-up effect
+    pub level: u32,               // Raised by picking up the same fruit again
+    pub locked: bool,             // Locked fruit is never pushed out by new pickups
 }
 
 /// Available power-up types that fruits can provide
@@ -46,32 +48,53 @@ impl PowerUpSlots {
             _ => PowerUpType::SpeedBoost,
         };
         
-        let fruit_slot = FruitSlot { fruit_type, powerup: dummy_powerup };
+        let fruit_slot = FruitSlot { fruit_type, powerup: dummy_powerup, level: 1, locked: false };
         
-        let dropped = if self.slots.len() >= self.max_slots {
-            self.slots.pop_back() // Remove oldest (back of deque)
-        } else {
-            None
-        };
-        
-        self.slots.push_front(fruit_slot); // Add newest to front
-        
-        dropped.map(|slot| slot.fruit_type)
+        self.push_front_unlocked(fruit_slot).map(|slot| slot.fruit_type)
     }
     
     /// Add a fruit power-up to the slots, maintaining visual fruit type info
     pub fn add_fruit(&mut self, fruit_type: u8, powerup: PowerUpType) -> Option<FruitSlot> {
-        let fruit_slot = FruitSlot { fruit_type, powerup };
+        let fruit_slot = FruitSlot { fruit_type, powerup, level: 1, locked: false };
         
-        let dropped = if self.slots.len() >= self.max_slots {
-            self.slots.pop_back() // Remove oldest (back of deque)
-        } else {
+        self.push_front_unlocked(fruit_slot)
+    }
+    
+    /// Adds the newest fruit to the first unlocked slot and shifts only unlocked fruit back,
+    /// so locked fruit keeps its slot. Returns the fruit pushed out of the last unlocked slot.
+    fn push_front_unlocked(&mut self, fruit_slot: FruitSlot) -> Option<FruitSlot> {
+        let mut carried = fruit_slot;
+        for slot in self.slots.iter_mut().filter(|slot| !slot.locked) {
+            carried = std::mem::replace(slot, carried);
+        }
+        if self.slots.len() < self.max_slots {
+            self.slots.push_back(carried);
             None
-        };
-        
-        self.slots.push_front(fruit_slot); // Add newest to front
-        
-        dropped
+        } else {
+            Some(carried)
+        }
+    }
+    
+    /// False when every slot is full and locked, so a new fruit has nowhere to go
+    pub fn can_add_fruit(&self) -> bool {
+        self.slots.len() < self.max_slots || self.slots.iter().any(|slot| !slot.locked)
+    }
+    
+    /// Raises the level of the fruit in `index`, up to `max_level`. Returns the new level.
+    pub fn level_up(&mut self, index: usize, max_level: u32) -> Option<u32> {
+        let slot = self.slots.get_mut(index)?;
+        if slot.level >= max_level {
+            return None;
+        }
+        slot.level += 1;
+        Some(slot.level)
+    }
+    
+    /// Locks or unlocks the fruit in `index`. Returns whether it is now locked.
+    pub fn toggle_lock(&mut self, index: usize) -> Option<bool> {
+        let slot = self.slots.get_mut(index)?;
+        slot.locked = !slot.locked;
+        Some(slot.locked)
     }
     
     /// Legacy method for backward compatibility - use add_fruit instead
@@ -21,6 +21,9 @@ pub fn register_all_abilities(registry: &mut AbilityRegistry) {
     
     // Coconut (6) abilities
     register_coconut_abilities(registry);
+    
+    // Ultimates for three copies of one fruit
+    register_ultimate_abilities(registry);
 }
 
 fn register_strawberry_abilities(registry: &mut AbilityRegistry) {
@@ -37,8 +40,15 @@ fn register_strawberry_abilities(registry: &mut AbilityRegistry) {
                 pierce_count: 0,
                 targeting: TargetingType::Nearest,
                 projectile_visual: ProjectileVisual::Strawberry,
+                wall_behavior: WallBehavior::Stop,
             }),
             visual_effect: VisualEffectType::Trail,
+            level_ups: vec![
+                LevelUp { damage: 2, ..default() },
+                LevelUp { count: 1, ..default() },
+                LevelUp { damage: 2, pierce: 1, ..default() },
+                LevelUp { damage: 3, count: 1, ..default() },
+            ],
         }
     );
     
@@ -55,8 +65,15 @@ fn register_strawberry_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 0.5,
                 duration: 5.0,
                 effect_type: AreaEffectType::SlowField, // Actually speeds up allies
+                stun_duration: 0.0,
             }),
             visual_effect: VisualEffectType::Aura,
+            level_ups: vec![
+                LevelUp { radius: 0.15, ..default() },
+                LevelUp { duration: 1.0, ..default() },
+                LevelUp { radius: 0.15, ..default() },
+                LevelUp { duration: 1.5, ..default() },
+            ],
         }
     );
     
@@ -73,8 +90,15 @@ fn register_strawberry_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 0.2,
                 duration: 3.0,
                 effect_type: AreaEffectType::BurnGround,
+                stun_duration: 0.0,
             }),
             visual_effect: VisualEffectType::Trail,
+            level_ups: vec![
+                LevelUp { damage: 1, ..default() },
+                LevelUp { radius: 0.2, ..default() },
+                LevelUp { damage: 1, duration: 1.0, ..default() },
+                LevelUp { damage: 2, radius: 0.2, ..default() },
+            ],
         }
     );
 }
@@ -93,6 +117,7 @@ fn register_pear_abilities(registry: &mut AbilityRegistry) {
                 pierce_count: 3,
                 targeting: TargetingType::Nearest,
                 projectile_visual: ProjectileVisual::Pear,
+                wall_behavior: WallBehavior::Bounce,
             }),
             visual_effect: VisualEffectType::Particles(ParticleConfig {
                 color: Color::srgb(0.7, 1.0, 0.3),
@@ -100,6 +125,12 @@ fn register_pear_abilities(registry: &mut AbilityRegistry) {
                 spread: 20.0,
                 lifetime: 0.5,
             }),
+            level_ups: vec![
+                LevelUp { damage: 4, ..default() },
+                LevelUp { pierce: 1, ..default() },
+                LevelUp { damage: 5, ..default() },
+                LevelUp { pierce: 1, count: 1, ..default() },
+            ],
         }
     );
     
@@ -116,8 +147,15 @@ fn register_pear_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 1.0,
                 duration: 0.1,
                 effect_type: AreaEffectType::HealingAura,
+                stun_duration: 0.0,
             }),
             visual_effect: VisualEffectType::Pulse,
+            level_ups: vec![
+                LevelUp { damage: -3, ..default() },
+                LevelUp { radius: 0.2, ..default() },
+                LevelUp { damage: -4, ..default() },
+                LevelUp { damage: -5, radius: 0.2, ..default() },
+            ],
         }
     );
     
@@ -134,8 +172,15 @@ fn register_pear_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 0.3,
                 duration: 4.0,
                 effect_type: AreaEffectType::SlowField,
+                stun_duration: 0.0,
             }),
             visual_effect: VisualEffectType::Trail,
+            level_ups: vec![
+                LevelUp { radius: 0.15, ..default() },
+                LevelUp { damage: 1, duration: 1.0, ..default() },
+                LevelUp { radius: 0.15, ..default() },
+                LevelUp { damage: 2, ..default() },
+            ],
         }
     );
 }
@@ -154,6 +199,7 @@ fn register_mango_abilities(registry: &mut AbilityRegistry) {
                 pierce_count: 0,
                 targeting: TargetingType::Nearest,
                 projectile_visual: ProjectileVisual::Mango,
+                wall_behavior: WallBehavior::Stop,
             }),
             visual_effect: VisualEffectType::Particles(ParticleConfig {
                 color: Color::srgb(1.0, 0.6, 0.0),
@@ -161,6 +207,12 @@ fn register_mango_abilities(registry: &mut AbilityRegistry) {
                 spread: 50.0,
                 lifetime: 1.0,
             }),
+            level_ups: vec![
+                LevelUp { damage: 8, ..default() },
+                LevelUp { count: 1, ..default() },
+                LevelUp { damage: 10, ..default() },
+                LevelUp { damage: 12, count: 1, ..default() },
+            ],
         }
     );
     
@@ -177,8 +229,15 @@ fn register_mango_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 0.5,
                 duration: 0.5,
                 effect_type: AreaEffectType::Explosion,
+                stun_duration: 0.0,
             }),
             visual_effect: VisualEffectType::Aura,
+            level_ups: vec![
+                LevelUp { damage: 2, ..default() },
+                LevelUp { radius: 0.15, ..default() },
+                LevelUp { damage: 3, ..default() },
+                LevelUp { radius: 0.2, ..default() },
+            ],
         }
     );
     
@@ -195,6 +254,7 @@ fn register_mango_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 0.5,
                 duration: 3.0,
                 effect_type: AreaEffectType::BurnGround,
+                stun_duration: 0.0,
             }),
             visual_effect: VisualEffectType::Particles(ParticleConfig {
                 color: Color::srgb(1.0, 0.3, 0.0),
@@ -202,6 +262,12 @@ fn register_mango_abilities(registry: &mut AbilityRegistry) {
                 spread: 25.0,
                 lifetime: 0.8,
             }),
+            level_ups: vec![
+                LevelUp { damage: 3, ..default() },
+                LevelUp { radius: 0.2, ..default() },
+                LevelUp { damage: 4, duration: 0.5, ..default() },
+                LevelUp { damage: 5, ..default() },
+            ],
         }
     );
 }
@@ -220,8 +286,15 @@ fn register_pineapple_abilities(registry: &mut AbilityRegistry) {
                 pierce_count: 1,
                 targeting: TargetingType::AllDirections,
                 projectile_visual: ProjectileVisual::Pineapple,
+                wall_behavior: WallBehavior::Stop,
             }),
             visual_effect: VisualEffectType::None,
+            level_ups: vec![
+                LevelUp { damage: 3, ..default() },
+                LevelUp { count: 4, ..default() },
+                LevelUp { pierce: 1, ..default() },
+                LevelUp { damage: 4, count: 4, ..default() },
+            ],
         }
     );
     
@@ -236,8 +309,15 @@ fn register_pineapple_abilities(registry: &mut AbilityRegistry) {
                 summon_type: SummonType::Shield,
                 duration: 5.0,
                 count: 3,
+                damage: 6,
             }),
             visual_effect: VisualEffectType::None,
+            level_ups: vec![
+                LevelUp { count: 1, ..default() },
+                LevelUp { damage: 2, ..default() },
+                LevelUp { count: 1, duration: 1.0, ..default() },
+                LevelUp { damage: 3, count: 1, ..default() },
+            ],
         }
     );
     
@@ -254,8 +334,15 @@ fn register_pineapple_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 0.2,
                 duration: 2.5,
                 effect_type: AreaEffectType::BurnGround,
+                stun_duration: 0.0,
             }),
             visual_effect: VisualEffectType::Trail,
+            level_ups: vec![
+                LevelUp { damage: 5, ..default() },
+                LevelUp { radius: 0.2, ..default() },
+                LevelUp { damage: 6, ..default() },
+                LevelUp { damage: 8, duration: 0.5, ..default() },
+            ],
         }
     );
 }
@@ -274,8 +361,15 @@ fn register_apple_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 0.2,
                 duration: 2.0,
                 effect_type: AreaEffectType::SlowField,
+                stun_duration: 0.0,
             }),
             visual_effect: VisualEffectType::Pulse,
+            level_ups: vec![
+                LevelUp { damage: 2, ..default() },
+                LevelUp { radius: 0.15, ..default() },
+                LevelUp { damage: 3, ..default() },
+                LevelUp { radius: 0.2, ..default() },
+            ],
         }
     );
     
@@ -289,8 +383,15 @@ fn register_apple_abilities(registry: &mut AbilityRegistry) {
             ability_type: AbilityType::Buff(BuffConfig {
                 stat_modifier: StatModifier::LifeSteal(0.3),
                 duration: 5.0,
+                stacking: ModifierStacking::Refresh,
             }),
             visual_effect: VisualEffectType::Aura,
+            level_ups: vec![
+                LevelUp { duration: 1.0, ..default() },
+                LevelUp { duration: 1.0, ..default() },
+                LevelUp { duration: 1.5, ..default() },
+                LevelUp { duration: 1.5, ..default() },
+            ],
         }
     );
     
@@ -307,8 +408,15 @@ fn register_apple_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 1.0,
                 duration: 0.2,
                 effect_type: AreaEffectType::Explosion,
+                stun_duration: 1.5,
             }),
             visual_effect: VisualEffectType::Pulse,
+            level_ups: vec![
+                LevelUp { damage: 10, ..default() },
+                LevelUp { radius: 0.15, ..default() },
+                LevelUp { damage: 12, ..default() },
+                LevelUp { damage: 15, radius: 0.15, ..default() },
+            ],
         }
     );
 }
@@ -327,8 +435,15 @@ fn register_carrot_abilities(registry: &mut AbilityRegistry) {
                 pierce_count: 5,
                 targeting: TargetingType::Forward,
                 projectile_visual: ProjectileVisual::Carrot,
+                wall_behavior: WallBehavior::PassThrough,
             }),
             visual_effect: VisualEffectType::Trail,
+            level_ups: vec![
+                LevelUp { damage: 5, ..default() },
+                LevelUp { pierce: 2, ..default() },
+                LevelUp { count: 1, ..default() },
+                LevelUp { damage: 8, pierce: 2, ..default() },
+            ],
         }
     );
     
@@ -345,8 +460,15 @@ fn register_carrot_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 0.5,
                 duration: 2.0,
                 effect_type: AreaEffectType::BurnGround,
+                stun_duration: 0.75,
             }),
             visual_effect: VisualEffectType::None,
+            level_ups: vec![
+                LevelUp { damage: 4, ..default() },
+                LevelUp { radius: 0.15, ..default() },
+                LevelUp { damage: 5, duration: 0.5, ..default() },
+                LevelUp { radius: 0.2, ..default() },
+            ],
         }
     );
     
@@ -360,8 +482,15 @@ fn register_carrot_abilities(registry: &mut AbilityRegistry) {
             ability_type: AbilityType::Buff(BuffConfig {
                 stat_modifier: StatModifier::ArmorBoost(100),
                 duration: 1.0,
+                stacking: ModifierStacking::Refresh,
             }),
             visual_effect: VisualEffectType::None,
+            level_ups: vec![
+                LevelUp { duration: 0.25, ..default() },
+                LevelUp { duration: 0.25, ..default() },
+                LevelUp { duration: 0.5, ..default() },
+                LevelUp { duration: 0.5, ..default() },
+            ],
         }
     );
 }
@@ -380,6 +509,7 @@ fn register_coconut_abilities(registry: &mut AbilityRegistry) {
                 pierce_count: 0,
                 targeting: TargetingType::Nearest,
                 projectile_visual: ProjectileVisual::Coconut,
+                wall_behavior: WallBehavior::Bounce,
             }),
             visual_effect: VisualEffectType::Particles(ParticleConfig {
                 color: Color::srgb(0.6, 0.4, 0.2),
@@ -387,6 +517,12 @@ fn register_coconut_abilities(registry: &mut AbilityRegistry) {
                 spread: 30.0,
                 lifetime: 0.8,
             }),
+            level_ups: vec![
+                LevelUp { damage: 10, ..default() },
+                LevelUp { pierce: 1, ..default() },
+                LevelUp { damage: 12, ..default() },
+                LevelUp { damage: 15, count: 1, ..default() },
+            ],
         }
     );
     
@@ -400,8 +536,15 @@ fn register_coconut_abilities(registry: &mut AbilityRegistry) {
             ability_type: AbilityType::Buff(BuffConfig {
                 stat_modifier: StatModifier::ArmorBoost(20),
                 duration: 6.0,
+                stacking: ModifierStacking::Additive,
             }),
             visual_effect: VisualEffectType::Aura,
+            level_ups: vec![
+                LevelUp { duration: 1.0, ..default() },
+                LevelUp { duration: 1.0, ..default() },
+                LevelUp { duration: 1.5, ..default() },
+                LevelUp { duration: 2.0, ..default() },
+            ],
         }
     );
     
@@ -418,8 +561,151 @@ fn register_coconut_abilities(registry: &mut AbilityRegistry) {
                 tick_rate: 1.0,
                 duration: 0.1,
                 effect_type: AreaEffectType::Explosion,
+                stun_duration: 1.0,
+            }),
+            visual_effect: VisualEffectType::Pulse,
+            level_ups: vec![
+                LevelUp { damage: 8, ..default() },
+                LevelUp { radius: 0.1, ..default() },
+                LevelUp { damage: 10, ..default() },
+                LevelUp { damage: 12, radius: 0.15, ..default() },
+            ],
+        }
+    );
+}
+
+fn register_ultimate_abilities(registry: &mut AbilityRegistry) {
+    // Strawberry Ultimate: Berry Storm - Piercing seeds in every direction
+    registry.abilities.insert(
+        AbilityId { fruit_type: 0, body_part: BodyPart::Ultimate },
+        AbilityDefinition {
+            name: "Berry Storm".to_string(),
+            description: "Unleashes a ring of piercing strawberries".to_string(),
+            cooldown: 6.0,
+            ability_type: AbilityType::Projectile(ProjectileConfig {
+                damage: 12,
+                speed: 450.0,
+                pierce_count: 2,
+                targeting: TargetingType::AllDirections,
+                projectile_visual: ProjectileVisual::Strawberry,
+                wall_behavior: WallBehavior::Stop,
+            }),
+            visual_effect: VisualEffectType::Trail,
+            level_ups: Vec::new(), // Ultimates come from a full set and never level
+        }
+    );
+    
+    // Pear Ultimate: Orchard Ricochet - Bouncing pears that keep going
+    registry.abilities.insert(
+        AbilityId { fruit_type: 1, body_part: BodyPart::Ultimate },
+        AbilityDefinition {
+            name: "Orchard Ricochet".to_string(),
+            description: "Pears spiral out and ricochet off walls".to_string(),
+            cooldown: 5.0,
+            ability_type: AbilityType::Projectile(ProjectileConfig {
+                damage: 18,
+                speed: 350.0,
+                pierce_count: 3,
+                targeting: TargetingType::Spiral,
+                projectile_visual: ProjectileVisual::Pear,
+                wall_behavior: WallBehavior::Bounce,
+            }),
+            visual_effect: VisualEffectType::Trail,
+            level_ups: Vec::new(),
+        }
+    );
+    
+    // Mango Ultimate: Mango Meteor - One huge stunning blast
+    registry.abilities.insert(
+        AbilityId { fruit_type: 2, body_part: BodyPart::Ultimate },
+        AbilityDefinition {
+            name: "Mango Meteor".to_string(),
+            description: "A molten mango crashes down around you".to_string(),
+            cooldown: 8.0,
+            ability_type: AbilityType::AreaEffect(AreaEffectConfig {
+                damage: 60,
+                radius: 180.0,
+                tick_rate: 0.2,
+                duration: 0.3, // Outlasts one tick so the blast lands exactly once
+                effect_type: AreaEffectType::Explosion,
+                stun_duration: 0.5,
             }),
             visual_effect: VisualEffectType::Pulse,
+            level_ups: Vec::new(),
+        }
+    );
+    
+    // Pineapple Ultimate: Crown of Thorns - A ring of spiked orbs
+    registry.abilities.insert(
+        AbilityId { fruit_type: 3, body_part: BodyPart::Ultimate },
+        AbilityDefinition {
+            name: "Crown of Thorns".to_string(),
+            description: "Six spiked orbs circle you".to_string(),
+            cooldown: 12.0,
+            ability_type: AbilityType::Summon(SummonConfig {
+                summon_type: SummonType::Orb,
+                duration: 8.0,
+                count: 6,
+                damage: 10,
+            }),
+            visual_effect: VisualEffectType::Aura,
+            level_ups: Vec::new(),
+        }
+    );
+    
+    // Apple Ultimate: Orchard Guardians - Minions that fight for you
+    registry.abilities.insert(
+        AbilityId { fruit_type: 4, body_part: BodyPart::Ultimate },
+        AbilityDefinition {
+            name: "Orchard Guardians".to_string(),
+            description: "Calls three apple guardians to fight at your side".to_string(),
+            cooldown: 15.0,
+            ability_type: AbilityType::Summon(SummonConfig {
+                summon_type: SummonType::Minion,
+                duration: 12.0,
+                count: 3,
+                damage: 12,
+            }),
+            visual_effect: VisualEffectType::None,
+            level_ups: Vec::new(),
+        }
+    );
+    
+    // Carrot Ultimate: Harvest Drill - A lance that goes through everything
+    registry.abilities.insert(
+        AbilityId { fruit_type: 5, body_part: BodyPart::Ultimate },
+        AbilityDefinition {
+            name: "Harvest Drill".to_string(),
+            description: "A giant carrot drills through enemies and walls".to_string(),
+            cooldown: 4.0,
+            ability_type: AbilityType::Projectile(ProjectileConfig {
+                damage: 45,
+                speed: 400.0,
+                pierce_count: 10,
+                targeting: TargetingType::Nearest,
+                projectile_visual: ProjectileVisual::Carrot,
+                wall_behavior: WallBehavior::PassThrough,
+            }),
+            visual_effect: VisualEffectType::Trail,
+            level_ups: Vec::new(),
+        }
+    );
+    
+    // Coconut Ultimate: Coconut Fortress - Turrets around the player
+    registry.abilities.insert(
+        AbilityId { fruit_type: 6, body_part: BodyPart::Ultimate },
+        AbilityDefinition {
+            name: "Coconut Fortress".to_string(),
+            description: "Plants three coconut turrets".to_string(),
+            cooldown: 14.0,
+            ability_type: AbilityType::Summon(SummonConfig {
+                summon_type: SummonType::Turret,
+                duration: 10.0,
+                count: 3,
+                damage: 10,
+            }),
+            visual_effect: VisualEffectType::None,
+            level_ups: Vec::new(),
         }
     );
 }
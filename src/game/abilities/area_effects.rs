@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 use crate::game::enemy::Enemy;
-use crate::game::combat::Health;
-use crate::game::player::{Player, PlayerController, SpeedBuff};
+use crate::game::combat::{Health, DamageImmunity};
+use crate::game::combat::effects::{ApplyStatusEvent, StatusKind};
+use crate::core::events::{CombatEvent, HitKind, HealEvent};
+use crate::game::combat::modifiers::{ApplyModifierEvent, ModifierStacking};
+use crate::game::player::Player;
 use crate::game::movement::Velocity;
 use super::*;
 
@@ -19,6 +22,14 @@ impl Plugin for AreaEffectPlugin {
     }
 }
 
+const AREA_STATUS_DURATION: f32 = 3.0; // Lingers after leaving the area
+const BURN_PATCH_RADIUS: f32 = 24.0;
+const BURN_PATCH_DAMAGE: i32 = 2;
+const BURN_PATCH_DURATION: f32 = 2.0;
+const SPEED_FIELD_SOURCE: &str = "Speed Field";
+const SPEED_FIELD_BOOST: f32 = 0.5;
+const SPEED_FIELD_LINGER: f32 = 0.2;
+
 #[derive(Component)]
 pub struct AreaEffect {
     pub damage_per_tick: i32,
@@ -27,6 +38,8 @@ pub struct AreaEffect {
     pub lifetime: Timer,
     pub owner: Entity,
     pub effect_type: AreaEffectType,
+    pub stun_duration: f32,
+    pub extra_effects: Vec<StatusKind>, // Talent statuses applied alongside the area's own
 }
 
 #[derive(Component)]
@@ -34,32 +47,23 @@ pub struct AreaVisual;
 
 fn apply_area_buffs(
     area_q: Query<(&Transform, &AreaEffect)>,
-    mut player_q: Query<(Entity, &Transform, &mut PlayerController), With<Player>>,
-    mut commands: Commands,
+    player_q: Query<(Entity, &Transform), With<Player>>,
+    mut modifier_events: EventWriter<ApplyModifierEvent>,
 ) {
-    for (player_entity, player_tf, mut controller) in player_q.iter_mut() {
-        let mut speed_multiplier = 1.0;
-        
+    for (player_entity, player_tf) in player_q.iter() {
         for (area_tf, area) in area_q.iter() {
             let distance = area_tf.translation.distance(player_tf.translation);
-            if distance <= area.radius {
-                match area.effect_type {
-                    AreaEffectType::SlowField => {
-                        // This is the strawberry speed field - boost player speed
-                        speed_multiplier *= 1.5; // 50% speed increase
-                    }
-                    _ => {}
-                }
+            // This is the strawberry speed field - boost player speed while standing in it
+            if distance <= area.radius && matches!(area.effect_type, AreaEffectType::SlowField) {
+                modifier_events.write(ApplyModifierEvent {
+                    target: player_entity,
+                    source: SPEED_FIELD_SOURCE.to_string(),
+                    modifier: StatModifier::SpeedBoost(SPEED_FIELD_BOOST),
+                    duration: SPEED_FIELD_LINGER,
+                    stacking: ModifierStacking::Refresh,
+                });
             }
         }
-        
-        // Apply the speed buff temporarily
-        if speed_multiplier > 1.0 {
-            commands.entity(player_entity).insert(SpeedBuff {
-                multiplier: speed_multiplier,
-                duration: Timer::from_seconds(0.2, TimerMode::Once), // Short duration
-            });
-        }
     }
 }
 
@@ -82,16 +86,18 @@ fn spawn_area_effects(
             
             commands.spawn((
                 AreaEffect {
-                    damage_per_tick: config.damage,
-                    radius: config.radius,
+                    damage_per_tick: config.damage + event.bonuses.damage,
+                    radius: config.radius * event.bonuses.radius_multiplier,
                     tick_timer: Timer::from_seconds(config.tick_rate, TimerMode::Repeating),
-                    lifetime: Timer::from_seconds(config.duration, TimerMode::Once),
+                    lifetime: Timer::from_seconds(config.duration + event.bonuses.duration, TimerMode::Once),
                     owner: event.caster,
                     effect_type: config.effect_type.clone(),
+                    stun_duration: config.stun_duration,
+                    extra_effects: event.bonuses.effects.clone(),
                 },
                 Sprite {
                     color,
-                    custom_size: Some(Vec2::splat(config.radius * 2.0)),
+                    custom_size: Some(Vec2::splat(config.radius * event.bonuses.radius_multiplier * 2.0)),
                     ..default()
                 },
                 Transform::from_translation(event.position + Vec3::new(0.0, 0.0, 1.0)),
@@ -101,6 +107,29 @@ fn spawn_area_effects(
     }
 }
 
+/// Small patch of burning ground, left behind by projectiles under the Jam Fire synergy
+pub fn spawn_burn_patch(commands: &mut Commands, position: Vec3, owner: Entity) {
+    commands.spawn((
+        AreaEffect {
+            damage_per_tick: BURN_PATCH_DAMAGE,
+            radius: BURN_PATCH_RADIUS,
+            tick_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+            lifetime: Timer::from_seconds(BURN_PATCH_DURATION, TimerMode::Once),
+            owner,
+            effect_type: AreaEffectType::BurnGround,
+            stun_duration: 0.0,
+            extra_effects: Vec::new(),
+        },
+        Sprite {
+            color: Color::srgba(1.0, 0.3, 0.0, 0.3),
+            custom_size: Some(Vec2::splat(BURN_PATCH_RADIUS * 2.0)),
+            ..default()
+        },
+        Transform::from_translation(position.truncate().extend(1.0)),
+        AreaVisual,
+    ));
+}
+
 fn update_area_effects(
     mut area_q: Query<(&mut AreaEffect, &mut Sprite)>,
     time: Res<Time>,
@@ -127,31 +156,67 @@ fn update_area_effects(
 fn apply_area_damage(
     area_q: Query<(&Transform, &AreaEffect)>,
     // Explicitly disjoint: enemies never include Player
-    mut enemy_q: Query<(&Transform, &mut Health), (With<Enemy>, Without<Player>)>,
+    enemy_q: Query<(Entity, &Transform, Has<DamageImmunity>), (With<Enemy>, Without<Player>)>,
     // Explicitly disjoint: players never include Enemy
-    mut player_q: Query<(&Transform, &mut Health), (With<Player>, Without<Enemy>)>,
+    mut player_q: Query<(Entity, &Transform, &mut Health), (With<Player>, Without<Enemy>)>,
+    mut combat_events: EventWriter<CombatEvent>,
+    mut heal_events: EventWriter<HealEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
 ) {
     for (area_tf, area) in area_q.iter() {
         if !area.tick_timer.just_finished() {
             continue;
         }
 
-        // Damage enemies
-        if area.damage_per_tick > 0 {
-            for (enemy_tf, mut enemy_health) in enemy_q.iter_mut() {
+        // Damage, stun and afflict enemies
+        let status = area.effect_type.status();
+        if area.damage_per_tick > 0 || area.stun_duration > 0.0 || status.is_some() || !area.extra_effects.is_empty() {
+            for (enemy_entity, enemy_tf, immune) in enemy_q.iter() {
                 let distance = area_tf.translation.distance(enemy_tf.translation);
-                if distance <= area.radius {
-                    enemy_health.take_damage(area.damage_per_tick);
+                if distance <= area.radius && !immune {
+                    if area.damage_per_tick > 0 {
+                        combat_events.write(CombatEvent {
+                            attacker: area.owner,
+                            target: enemy_entity,
+                            damage: area.damage_per_tick,
+                            damage_type: area.effect_type.damage_type(),
+                            position: enemy_tf.translation,
+                            kind: HitKind::Tick,
+                        });
+                    }
+                    if area.stun_duration > 0.0 {
+                        status_events.write(ApplyStatusEvent {
+                            target: enemy_entity,
+                            source: area.owner,
+                            kind: StatusKind::Stun,
+                            duration: area.stun_duration,
+                            stacks: 1,
+                        });
+                    }
+                    for kind in status.iter().chain(&area.extra_effects) {
+                        status_events.write(ApplyStatusEvent {
+                            target: enemy_entity,
+                            source: area.owner,
+                            kind: *kind,
+                            duration: AREA_STATUS_DURATION,
+                            stacks: 1,
+                        });
+                    }
                 }
             }
         }
 
         // Heal player (negative damage)
         if area.damage_per_tick < 0 {
-            for (player_tf, mut player_health) in player_q.iter_mut() {
+            for (player_entity, player_tf, mut player_health) in player_q.iter_mut() {
                 let distance = area_tf.translation.distance(player_tf.translation);
                 if distance <= area.radius {
-                    player_health.heal((-area.damage_per_tick) as i32);
+                    player_health.heal(-area.damage_per_tick);
+                    heal_events.write(HealEvent {
+                        target: player_entity,
+                        amount: -area.damage_per_tick,
+                        position: player_tf.translation,
+                    });
                 }
             }
         }
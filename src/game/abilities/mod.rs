@@ -3,13 +3,21 @@ pub mod projectile_system;
 pub mod area_effects;
 pub mod ability_visuals;
 pub mod test_setup;
+pub mod summons;
+pub mod synergies;
 
 use bevy::prelude::*;
 use crate::entities::powerup::PowerUpSlots;
 use crate::game::player::Player;
-use crate::systems::talents::PlayerTalents;
+use crate::core::config::GameConfig;
+use crate::core::events::DamageType;
+use crate::core::input::{Action, InputBuffer, buffer_input_system};
+use crate::game::combat::effects::{StatusKind, StatusEffects};
+use crate::game::combat::modifiers::{ApplyModifierEvent, ModifierStacking};
+use crate::systems::talents::AbilityModification;
 use crate::systems::shop::PlayerCurrency;
 use std::collections::HashMap;
+use std::time::Duration;
 
 pub struct AbilitiesPlugin;
 
@@ -17,18 +25,21 @@ impl Plugin for AbilitiesPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<AbilityRegistry>()
+            .init_resource::<AbilityModifiers>()
             .add_event::<TriggerAbilityEvent>()
             .add_plugins((
                 projectile_system::ProjectilePlugin,
                 area_effects::AreaEffectPlugin,
                 ability_visuals::AbilityVisualsPlugin,
                 test_setup::AbilityTestPlugin,
+                summons::SummonPlugin,
+                synergies::SynergyPlugin,
             ))
             .add_systems(Startup, setup_ability_registry)
             .add_systems(Update, (
                 update_player_abilities,
-                trigger_abilities,
-                update_ability_cooldowns,
+                execute_ability_slots.after(buffer_input_system),
+                apply_buff_abilities,
             ).chain());
     }
 }
@@ -39,6 +50,7 @@ pub struct ActiveAbilities {
     pub head_ability: Option<AbilityInstance>,
     pub torso_ability: Option<AbilityInstance>,
     pub legs_ability: Option<AbilityInstance>,
+    pub ultimate_ability: Option<AbilityInstance>, // Evolved ability from three copies of one fruit
 }
 
 /// Instance of an active ability with its cooldown
@@ -47,6 +59,54 @@ pub struct AbilityInstance {
     pub ability_id: AbilityId,
     pub cooldown_timer: Timer,
     pub auto_cast: bool,
+    pub level: u32,
+}
+
+impl AbilityInstance {
+    /// Manual abilities start ready and wait for their key; auto-cast ones fire every cooldown
+    pub fn new(ability_id: AbilityId, cooldown: f32, auto_cast: bool, level: u32) -> Self {
+        let mut cooldown_timer = Timer::from_seconds(cooldown, TimerMode::Once);
+        if !auto_cast {
+            let duration = cooldown_timer.duration();
+            cooldown_timer.tick(duration);
+        }
+        Self {
+            ability_id,
+            cooldown_timer,
+            auto_cast,
+            level,
+        }
+    }
+
+    /// Changes the cooldown length, keeping how far through it the ability is
+    pub fn set_cooldown(&mut self, cooldown: f32) {
+        let duration = Duration::from_secs_f32(cooldown.max(0.0));
+        if self.cooldown_timer.duration() != duration {
+            let progress = self.cooldown_timer.fraction();
+            self.cooldown_timer = Timer::new(duration, TimerMode::Once);
+            self.set_progress(progress);
+        }
+    }
+
+    /// Restarts the cooldown at `progress` (0 just cast, 1 ready)
+    pub fn set_progress(&mut self, progress: f32) {
+        self.cooldown_timer.reset();
+        let duration = self.cooldown_timer.duration();
+        self.cooldown_timer.tick(duration.mul_f32(progress.clamp(0.0, 1.0)));
+    }
+}
+
+impl ActiveAbilities {
+    /// Slot 0 is the head, 1 the torso, 2 the legs and 3 the ultimate
+    pub fn slot_mut(&mut self, slot: u8) -> Option<&mut AbilityInstance> {
+        match slot {
+            0 => self.head_ability.as_mut(),
+            1 => self.torso_ability.as_mut(),
+            2 => self.legs_ability.as_mut(),
+            3 => self.ultimate_ability.as_mut(),
+            _ => None,
+        }
+    }
 }
 
 /// Unique identifier for each ability combination
@@ -61,6 +121,7 @@ pub enum BodyPart {
     Head,
     Torso,
     Legs,
+    Ultimate,
 }
 
 /// Resource that stores all ability definitions
@@ -69,6 +130,19 @@ pub struct AbilityRegistry {
     pub abilities: HashMap<AbilityId, AbilityDefinition>,
 }
 
+impl AbilityRegistry {
+    /// Highest level the fruit in PowerUpSlots index `slot` can reach there
+    pub fn max_level(&self, fruit_type: u8, slot: usize) -> u32 {
+        let body_part = match slot {
+            0 => BodyPart::Head,
+            1 => BodyPart::Torso,
+            2 => BodyPart::Legs,
+            _ => return 1,
+        };
+        self.abilities.get(&AbilityId { fruit_type, body_part }).map_or(1, AbilityDefinition::max_level)
+    }
+}
+
 /// Definition of an ability's properties
 #[derive(Clone)]
 pub struct AbilityDefinition {
@@ -77,6 +151,36 @@ pub struct AbilityDefinition {
     pub cooldown: f32,
     pub ability_type: AbilityType,
     pub visual_effect: VisualEffectType,
+    pub level_ups: Vec<LevelUp>, // Gains at levels 2, 3 and so on, from picking up the fruit again
+}
+
+impl AbilityDefinition {
+    /// Level 1 is the base ability; each level-up entry adds one more
+    pub fn max_level(&self) -> u32 {
+        self.level_ups.len() as u32 + 1
+    }
+
+    /// Everything the ability gains on its way to `level`
+    pub fn level_gains(&self, level: u32) -> LevelUp {
+        let levels = level.saturating_sub(1) as usize;
+        self.level_ups.iter().take(levels).fold(LevelUp::default(), |total, gain| LevelUp {
+            damage: total.damage + gain.damage,
+            pierce: total.pierce + gain.pierce,
+            radius: total.radius + gain.radius,
+            count: total.count + gain.count,
+            duration: total.duration + gain.duration,
+        })
+    }
+}
+
+/// What one ability level adds on top of the levels before it
+#[derive(Clone, Copy, Default)]
+pub struct LevelUp {
+    pub damage: i32,   // Per hit or tick; negative heals more on healing areas
+    pub pierce: u32,
+    pub radius: f32,   // Fraction of the base radius
+    pub count: u32,    // Extra projectiles or summons
+    pub duration: f32, // Seconds added to areas, buffs and summons
 }
 
 #[derive(Clone)]
@@ -94,6 +198,15 @@ pub struct ProjectileConfig {
     pub pierce_count: u32,
     pub targeting: TargetingType,
     pub projectile_visual: ProjectileVisual,
+    pub wall_behavior: WallBehavior,
+}
+
+/// What a projectile does when it reaches a wall tile
+#[derive(Clone, Copy, PartialEq)]
+pub enum WallBehavior {
+    Stop,
+    Bounce,
+    PassThrough,
 }
 
 #[derive(Clone)]
@@ -103,12 +216,14 @@ pub struct AreaEffectConfig {
     pub tick_rate: f32,
     pub duration: f32,
     pub effect_type: AreaEffectType,
+    pub stun_duration: f32, // Enemies caught in a tick are stunned this long, 0 for none
 }
 
 #[derive(Clone)]
 pub struct BuffConfig {
     pub stat_modifier: StatModifier,
     pub duration: f32,
+    pub stacking: ModifierStacking, // What recasting does while the buff is still up
 }
 
 #[derive(Clone)]
@@ -116,6 +231,7 @@ pub struct SummonConfig {
     pub summon_type: SummonType,
     pub duration: f32,
     pub count: u32,
+    pub damage: i32, // Per turret bolt, orb or shield touch, or minion hit
 }
 
 #[derive(Clone)]
@@ -139,6 +255,16 @@ pub enum ProjectileVisual {
     Energy,
 }
 
+impl ProjectileVisual {
+    /// Thrown fruit hits physically; energy bolts are magic
+    pub fn damage_type(&self) -> DamageType {
+        match self {
+            ProjectileVisual::Energy => DamageType::Magic,
+            _ => DamageType::Physical,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum AreaEffectType {
     Explosion,
@@ -148,6 +274,40 @@ pub enum AreaEffectType {
     BurnGround,
 }
 
+impl AbilityType {
+    /// Name talents use to target every ability of this type
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AbilityType::Projectile(_) => "projectile",
+            AbilityType::AreaEffect(_) => "area",
+            AbilityType::Buff(_) => "buff",
+            AbilityType::Summon(_) => "summon",
+        }
+    }
+}
+
+impl AreaEffectType {
+    pub fn damage_type(&self) -> DamageType {
+        match self {
+            AreaEffectType::Explosion => DamageType::Fire,
+            AreaEffectType::PoisonCloud => DamageType::Poison,
+            AreaEffectType::SlowField => DamageType::Ice,
+            AreaEffectType::BurnGround => DamageType::Fire,
+            AreaEffectType::HealingAura => DamageType::Magic,
+        }
+    }
+
+    /// Status applied to enemies caught in the area each tick
+    pub fn status(&self) -> Option<StatusKind> {
+        match self {
+            AreaEffectType::PoisonCloud => Some(StatusKind::Poison),
+            AreaEffectType::SlowField => Some(StatusKind::Slow),
+            AreaEffectType::BurnGround => Some(StatusKind::Burn),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum StatModifier {
     SpeedBoost(f32),
@@ -156,7 +316,7 @@ pub enum StatModifier {
     LifeSteal(f32),
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum SummonType {
     Turret,
     Orb,
@@ -186,137 +346,209 @@ pub struct TriggerAbilityEvent {
     pub ability_id: AbilityId,
     pub caster: Entity,
     pub position: Vec3,
+    pub bonuses: AbilityBonuses,
+}
+
+const MIN_COOLDOWN_MULTIPLIER: f32 = 0.25; // Cooldowns never drop below a quarter
+const ADDED_EFFECT_DURATION: f32 = 3.0; // For statuses talents add to abilities
+
+/// Talent changes to abilities, kept up to date by the talent system
+#[derive(Resource, Default)]
+pub struct AbilityModifiers {
+    pub cooldown_reduction: f32,
+    /// Each entry targets an ability kind ("projectile", "area", "buff", "summon"),
+    /// a lowercase ability name, or "all"
+    pub modifications: Vec<(String, AbilityModification)>,
+    /// Same as `modifications`, from the fruit pair synergies currently slotted
+    pub synergy_modifications: Vec<(String, AbilityModification)>,
+    pub projectile_burn_ground: bool, // Projectile hits leave burning ground
+}
+
+impl AbilityModifiers {
+    fn applying_to<'a>(&'a self, definition: &'a AbilityDefinition) -> impl Iterator<Item = &'a AbilityModification> {
+        self.modifications.iter()
+            .chain(&self.synergy_modifications)
+            .filter(|(target, _)| {
+                target == "all" || target == definition.ability_type.kind() || *target == definition.name.to_lowercase()
+            })
+            .map(|(_, modification)| modification)
+    }
+
+    pub fn cooldown_multiplier(&self, definition: &AbilityDefinition) -> f32 {
+        let reduction = self.cooldown_reduction + self.applying_to(definition)
+            .map(|modification| match modification {
+                AbilityModification::ReduceCooldown(amount) => *amount,
+                _ => 0.0,
+            })
+            .sum::<f32>();
+        (1.0 - reduction).max(MIN_COOLDOWN_MULTIPLIER)
+    }
+
+    pub fn bonuses(&self, definition: &AbilityDefinition) -> AbilityBonuses {
+        let mut bonuses = AbilityBonuses {
+            burn_ground: self.projectile_burn_ground && matches!(definition.ability_type, AbilityType::Projectile(_)),
+            ..default()
+        };
+        for modification in self.applying_to(definition) {
+            match modification {
+                AbilityModification::ExtraProjectiles(count) => bonuses.extra_projectiles += count,
+                AbilityModification::IncreaseRadius(amount) => bonuses.radius_multiplier += amount,
+                AbilityModification::AddEffect(name) => match StatusKind::from_name(name) {
+                    Some(kind) => bonuses.effects.push(kind),
+                    None => println!("Unknown ability effect '{}'", name),
+                },
+                AbilityModification::ReduceCooldown(_) => {}
+            }
+        }
+        bonuses
+    }
+}
+
+/// Talent, synergy and level bonuses carried by a single cast
+#[derive(Clone)]
+pub struct AbilityBonuses {
+    pub extra_projectiles: u32, // Also extra summons for summon abilities
+    pub radius_multiplier: f32,
+    pub effects: Vec<StatusKind>, // Applied to every enemy the cast hits
+    pub burn_ground: bool,
+    pub damage: i32,
+    pub pierce: u32,
+    pub duration: f32,
+}
+
+impl Default for AbilityBonuses {
+    fn default() -> Self {
+        Self {
+            extra_projectiles: 0,
+            radius_multiplier: 1.0,
+            effects: Vec::new(),
+            burn_ground: false,
+            damage: 0,
+            pierce: 0,
+            duration: 0.0,
+        }
+    }
+}
+
+impl AbilityBonuses {
+    pub fn add_level(&mut self, gains: LevelUp) {
+        self.damage += gains.damage;
+        self.pierce += gains.pierce;
+        self.radius_multiplier += gains.radius;
+        self.extra_projectiles += gains.count;
+        self.duration += gains.duration;
+    }
 }
 
 fn setup_ability_registry(mut registry: ResMut<AbilityRegistry>) {
     use ability_types::*;
     
-    // Register all 21 abilities (3 parts × 7 fruits)
+    // Register all 21 abilities (3 parts × 7 fruits) plus the 7 ultimates
     register_all_abilities(&mut registry);
 }
 
 fn update_player_abilities(
     mut player_q: Query<(&PowerUpSlots, &mut ActiveAbilities), (With<Player>, Changed<PowerUpSlots>)>,
     registry: Res<AbilityRegistry>,
+    config: Res<GameConfig>,
 ) {
     for (powerup_slots, mut active_abilities) in player_q.iter_mut() {
-        // Update head ability
-        if let Some(fruit_type) = powerup_slots.get_head_fruit() {
-            let ability_id = AbilityId {
-                fruit_type,
-                body_part: BodyPart::Head,
-            };
-            
-            if let Some(definition) = registry.abilities.get(&ability_id) {
-                active_abilities.head_ability = Some(AbilityInstance {
-                    ability_id,
-                    cooldown_timer: Timer::from_seconds(definition.cooldown, TimerMode::Repeating),
-                    auto_cast: true,
-                });
-            }
-        } else {
-            active_abilities.head_ability = None;
-        }
-        
-        // Update torso ability
-        if let Some(fruit_type) = powerup_slots.get_torso_fruit() {
-            let ability_id = AbilityId {
-                fruit_type,
-                body_part: BodyPart::Torso,
-            };
-            
-            if let Some(definition) = registry.abilities.get(&ability_id) {
-                active_abilities.torso_ability = Some(AbilityInstance {
-                    ability_id,
-                    cooldown_timer: Timer::from_seconds(definition.cooldown, TimerMode::Repeating),
-                    auto_cast: true,
-                });
-            }
-        } else {
-            active_abilities.torso_ability = None;
-        }
-        
-        // Update legs ability
-        if let Some(fruit_type) = powerup_slots.get_legs_fruit() {
-            let ability_id = AbilityId {
-                fruit_type,
-                body_part: BodyPart::Legs,
-            };
-            
-            if let Some(definition) = registry.abilities.get(&ability_id) {
-                active_abilities.legs_ability = Some(AbilityInstance {
-                    ability_id,
-                    cooldown_timer: Timer::from_seconds(definition.cooldown, TimerMode::Repeating),
-                    auto_cast: true,
-                });
-            }
-        } else {
-            active_abilities.legs_ability = None;
-        }
+        let auto_cast = config.auto_cast_abilities;
+        let level = |index: usize| powerup_slots.slots.get(index).map_or(1, |slot| slot.level);
+        refresh_slot(&mut active_abilities.head_ability, powerup_slots.get_head_fruit(), level(0), BodyPart::Head, &registry, auto_cast);
+        refresh_slot(&mut active_abilities.torso_ability, powerup_slots.get_torso_fruit(), level(1), BodyPart::Torso, &registry, auto_cast);
+        refresh_slot(&mut active_abilities.legs_ability, powerup_slots.get_legs_fruit(), level(2), BodyPart::Legs, &registry, auto_cast);
+        refresh_slot(&mut active_abilities.ultimate_ability, synergies::full_set(powerup_slots), 1, BodyPart::Ultimate, &registry, auto_cast);
+    }
+}
+
+/// Points a slot at its fruit's ability. An unchanged ability keeps its timer, and a new one
+/// picks up the old cooldown progress so swapping fruit never resets a cooldown.
+/// The fruit's level carries over to whichever ability it currently grants.
+fn refresh_slot(
+    slot: &mut Option<AbilityInstance>,
+    fruit_type: Option<u8>,
+    level: u32,
+    body_part: BodyPart,
+    registry: &AbilityRegistry,
+    auto_cast: bool,
+) {
+    let Some(fruit_type) = fruit_type else {
+        *slot = None;
+        return;
+    };
+    let ability_id = AbilityId { fruit_type, body_part };
+    let Some(definition) = registry.abilities.get(&ability_id) else { return };
+    let level = level.min(definition.max_level());
+    if let Some(ability) = slot.as_mut().filter(|ability| ability.ability_id == ability_id) {
+        ability.level = level;
+        return;
+    }
+
+    let mut instance = AbilityInstance::new(ability_id, definition.cooldown, auto_cast, level);
+    if let Some(previous) = slot.as_ref() {
+        instance.set_progress(previous.cooldown_timer.fraction());
     }
+    *slot = Some(instance);
 }
 
-fn trigger_abilities(
-    mut player_q: Query<(Entity, &Transform, &mut ActiveAbilities), With<Player>>,
+/// Runs every ability slot the same way: talent cooldown reduction, auto-cast or key press,
+/// and the talent modifications and level gains that travel with the cast. Talent damage
+/// bonuses reach ability hits through DamageModifiers when they resolve.
+fn execute_ability_slots(
+    mut player_q: Query<(Entity, &Transform, &mut ActiveAbilities, &StatusEffects), With<Player>>,
+    registry: Res<AbilityRegistry>,
+    modifiers: Res<AbilityModifiers>,
+    mut buffer: ResMut<InputBuffer>,
     mut trigger_events: EventWriter<TriggerAbilityEvent>,
-    talents: Res<PlayerTalents>,
     time: Res<Time>,
 ) {
-    // Calculate talent bonuses
-    let cooldown_reduction = talents.get_talent_bonus("cooldown_reduction").unwrap_or(0.0);
-    let damage_bonus = talents.get_talent_bonus("damage_increase").unwrap_or(0.0);
-    
-    for (entity, transform, mut abilities) in player_q.iter_mut() {
-        // Check and trigger head ability
-        if let Some(ref mut ability) = abilities.head_ability {
-            // Apply cooldown reduction from talents
-            let mut modified_timer = ability.cooldown_timer.clone();
-            if cooldown_reduction > 0.0 {
-                let reduced_duration = modified_timer.duration().as_secs_f32() * (1.0 - cooldown_reduction);
-                modified_timer.set_duration(std::time::Duration::from_secs_f32(reduced_duration));
-            }
-            modified_timer.tick(time.delta());
-            
-            if modified_timer.just_finished() && ability.auto_cast {
-                trigger_events.send(TriggerAbilityEvent {
-                    ability_id: ability.ability_id,
-                    caster: entity,
-                    position: transform.translation,
-                });
-                ability.cooldown_timer.reset();
-            }
+    let Ok((entity, transform, mut abilities, statuses)) = player_q.single_mut() else { return };
+
+    for slot in 0..4 {
+        let Some(ability) = abilities.slot_mut(slot) else { continue };
+        let Some(definition) = registry.abilities.get(&ability.ability_id) else { continue };
+
+        ability.set_cooldown(definition.cooldown * modifiers.cooldown_multiplier(definition));
+        ability.cooldown_timer.tick(time.delta());
+        if !ability.cooldown_timer.finished() || statuses.is_disabled() {
+            continue;
         }
-        
-        // Check and trigger torso ability
-        if let Some(ref mut ability) = abilities.torso_ability {
-            ability.cooldown_timer.tick(time.delta());
-            if ability.cooldown_timer.just_finished() && ability.auto_cast {
-                trigger_events.send(TriggerAbilityEvent {
-                    ability_id: ability.ability_id,
-                    caster: entity,
-                    position: transform.translation,
-                });
-            }
-        }
-        
-        // Check and trigger legs ability
-        if let Some(ref mut ability) = abilities.legs_ability {
-            ability.cooldown_timer.tick(time.delta());
-            if ability.cooldown_timer.just_finished() && ability.auto_cast {
-                trigger_events.send(TriggerAbilityEvent {
-                    ability_id: ability.ability_id,
-                    caster: entity,
-                    position: transform.translation,
-                });
-            }
+        // Manual presses during the cooldown stay buffered and fire once it's ready
+        if !ability.auto_cast && !buffer.consume(Action::UseAbility(slot), time.elapsed_secs()) {
+            continue;
         }
+
+        let mut bonuses = modifiers.bonuses(definition);
+        bonuses.add_level(definition.level_gains(ability.level));
+        trigger_events.write(TriggerAbilityEvent {
+            ability_id: ability.ability_id,
+            caster: entity,
+            position: transform.translation,
+            bonuses,
+        });
+        ability.cooldown_timer.reset();
     }
 }
 
-fn update_ability_cooldowns(
-    _time: Res<Time>,
+/// Buff abilities put their modifier on the caster
+fn apply_buff_abilities(
+    mut events: EventReader<TriggerAbilityEvent>,
+    registry: Res<AbilityRegistry>,
+    mut modifier_events: EventWriter<ApplyModifierEvent>,
 ) {
-    // Additional cooldown management if needed
+    for event in events.read() {
+        let Some(definition) = registry.abilities.get(&event.ability_id) else { continue };
+        if let AbilityType::Buff(ref config) = definition.ability_type {
+            modifier_events.write(ApplyModifierEvent {
+                target: event.caster,
+                source: definition.name.clone(),
+                modifier: config.stat_modifier.clone(),
+                duration: config.duration + event.bonuses.duration,
+                stacking: config.stacking,
+            });
+        }
+    }
 }
 
 pub mod cooldown_display;
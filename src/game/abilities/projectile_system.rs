@@ -1,8 +1,11 @@
 use bevy::prelude::*;
 use crate::game::enemy::Enemy;
-use crate::game::combat::Health;
+use crate::core::events::{CombatEvent, HitKind};
+use crate::game::combat::effects::ApplyStatusEvent;
 use crate::game::movement::{Velocity, Collider};
+use crate::world::tile_grid::TileGrid;
 use super::*;
+use super::area_effects::spawn_burn_patch;
 use super::ability_visuals::{AnimatedSprite, ProjectileVisualEffect, FruitVisualAssets, TrailEffect};
 
 pub struct ProjectilePlugin;
@@ -14,6 +17,7 @@ impl Plugin for ProjectilePlugin {
             (
                 spawn_projectiles,
                 update_projectiles,
+                handle_projectile_wall_hits,
                 handle_projectile_collisions,
                 cleanup_expired_projectiles,
                 update_projectile_rotation,
@@ -26,10 +30,14 @@ impl Plugin for ProjectilePlugin {
 #[derive(Component)]
 pub struct Projectile {
     pub damage: i32,
+    pub damage_type: DamageType,
     pub pierce_remaining: u32,
     pub lifetime: Timer,
     pub owner: Entity,
     pub hit_entities: Vec<Entity>,
+    pub wall_behavior: WallBehavior,
+    pub on_hit: Vec<StatusKind>, // Statuses from talents, applied with the damage
+    pub burn_ground: bool,       // Leaves a burning patch where it hits
 }
 
 #[derive(Component)]
@@ -37,6 +45,8 @@ pub struct HomingProjectile {
     pub turn_speed: f32,
 }
 
+const EXTRA_PROJECTILE_SPREAD: f32 = 0.2; // Radians between fanned-out extra shots
+
 fn spawn_projectiles(
     mut commands: Commands,
     mut events: EventReader<TriggerAbilityEvent>,
@@ -48,7 +58,8 @@ fn spawn_projectiles(
         let Some(definition) = registry.abilities.get(&event.ability_id) else { continue };
 
         if let AbilityType::Projectile(ref config) = definition.ability_type {
-            match config.targeting {
+            let extra = event.bonuses.extra_projectiles;
+            let directions = match config.targeting {
                 TargetingType::Nearest => {
                     let mut nearest_enemy = None;
                     let mut nearest_distance = f32::MAX;
@@ -61,67 +72,52 @@ fn spawn_projectiles(
                         }
                     }
 
-                    if let Some(target_pos) = nearest_enemy {
-                        let direction3 = (target_pos - event.position).normalize_or_zero();
-                        spawn_enhanced_projectile(
-                            &mut commands,
-                            &assets,
-                            event.position,
-                            direction3.truncate(),
-                            config,
-                            event.caster,
-                            event.ability_id.fruit_type,
-                        );
-                    }
-                }
-                TargetingType::AllDirections => {
-                    for i in 0..8 {
-                        let angle = (i as f32) * std::f32::consts::TAU / 8.0;
-                        let direction = Vec2::new(angle.cos(), angle.sin());
-                        spawn_enhanced_projectile(
-                            &mut commands,
-                            &assets,
-                            event.position,
-                            direction,
-                            config,
-                            event.caster,
-                            event.ability_id.fruit_type,
-                        );
+                    match nearest_enemy {
+                        Some(target_pos) => fan((target_pos - event.position).truncate(), extra),
+                        None => Vec::new(),
                     }
                 }
-                TargetingType::Forward => {
-                    let direction = Vec2::X;
-                    spawn_enhanced_projectile(
-                        &mut commands,
-                        &assets,
-                        event.position,
-                        direction,
-                        config,
-                        event.caster,
-                        event.ability_id.fruit_type,
-                    );
-                }
-                TargetingType::Spiral => {
-                    for i in 0..3 {
-                        let angle = (i as f32) * std::f32::consts::TAU / 3.0;
-                        let direction = Vec2::new(angle.cos(), angle.sin());
-                        spawn_enhanced_projectile(
-                            &mut commands,
-                            &assets,
-                            event.position,
-                            direction,
-                            config,
-                            event.caster,
-                            event.ability_id.fruit_type,
-                        );
-                    }
-                }
-                _ => {}
+                TargetingType::AllDirections => ring(8 + extra),
+                TargetingType::Forward => fan(Vec2::X, extra),
+                TargetingType::Spiral => ring(3 + extra),
+                _ => Vec::new(),
+            };
+
+            for direction in directions {
+                spawn_enhanced_projectile(
+                    &mut commands,
+                    &assets,
+                    event.position,
+                    direction,
+                    config,
+                    event.caster,
+                    event.ability_id.fruit_type,
+                    &event.bonuses,
+                );
             }
         }
     }
 }
 
+/// Evenly spaced directions around the caster
+fn ring(count: u32) -> Vec<Vec2> {
+    (0..count)
+        .map(|i| Vec2::from_angle(i as f32 * std::f32::consts::TAU / count as f32))
+        .collect()
+}
+
+/// The aimed direction plus `extra` shots fanned out alternately to either side
+fn fan(direction: Vec2, extra: u32) -> Vec<Vec2> {
+    let direction = direction.normalize_or_zero();
+    (0..=extra)
+        .map(|i| {
+            let side = if i % 2 == 0 { -1.0 } else { 1.0 };
+            let angle = side * ((i + 1) / 2) as f32 * EXTRA_PROJECTILE_SPREAD;
+            Vec2::from_angle(angle).rotate(direction)
+        })
+        .collect()
+}
+
 fn spawn_enhanced_projectile(
     commands: &mut Commands,
     assets: &FruitVisualAssets,
@@ -130,6 +126,7 @@ fn spawn_enhanced_projectile(
     config: &ProjectileConfig,
     owner: Entity,
     fruit_type: u8,
+    bonuses: &AbilityBonuses,
 ) {
     let vel = direction.normalize_or_zero() * config.speed;
     
@@ -168,11 +165,15 @@ fn spawn_enhanced_projectile(
 
     let projectile_entity = commands.spawn((
         Projectile {
-            damage: config.damage,
-            pierce_remaining: config.pierce_count,
+            damage: config.damage + bonuses.damage,
+            damage_type: config.projectile_visual.damage_type(),
+            pierce_remaining: config.pierce_count + bonuses.pierce,
             lifetime: Timer::from_seconds(5.0, TimerMode::Once),
             owner,
             hit_entities: Vec::new(),
+            wall_behavior: config.wall_behavior,
+            on_hit: bonuses.effects.clone(),
+            burn_ground: bonuses.burn_ground,
         },
         Velocity(vel),
         Collider { size: base_size },
@@ -244,7 +245,7 @@ fn update_projectiles(
     }
 }
 
-fn find_nearest_enemy(
+pub(super) fn find_nearest_enemy(
     position: &Vec3,
     enemy_q: &Query<&Transform, (With<Enemy>, Without<Projectile>)>,
 ) -> Option<Vec3> {
@@ -288,13 +289,61 @@ fn update_projectile_rotation(
     }
 }
 
+// Stops or bounces projectiles that entered a wall tile, depending on their WallBehavior
+fn handle_projectile_wall_hits(
+    mut commands: Commands,
+    mut projectile_q: Query<(Entity, &mut Transform, &mut Velocity, &Projectile)>,
+    grid: Res<TileGrid>,
+) {
+    for (entity, mut transform, mut velocity, projectile) in projectile_q.iter_mut() {
+        if projectile.wall_behavior == WallBehavior::PassThrough {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let cell = grid.world_to_cell(position);
+        if grid.is_cell_walkable(cell) {
+            continue;
+        }
+
+        match projectile.wall_behavior {
+            WallBehavior::Stop => {
+                spawn_impact_effect(&mut commands, transform.translation, projectile.damage);
+                commands.entity(entity).despawn();
+            }
+            WallBehavior::Bounce => {
+                // Reflect off the face we came through: the axis whose back-step leads to open ground.
+                // Axes we aren't moving along never step, so straight shots only flip their own axis.
+                let axis_step = |speed: f32| if speed == 0.0 { 0.0 } else { speed.signum() * grid.tile_size };
+                let step = Vec2::new(axis_step(velocity.0.x), axis_step(velocity.0.y));
+                let back_x = step.x != 0.0 && grid.is_walkable(position - Vec2::new(step.x, 0.0));
+                let back_y = step.y != 0.0 && grid.is_walkable(position - Vec2::new(0.0, step.y));
+                // Neither or both back-steps being open means a corner, which sends it back both ways
+                let corner = back_x == back_y;
+                // Flipped axes also step back out so the projectile doesn't bounce again inside the wall
+                if back_x || corner {
+                    velocity.0.x = -velocity.0.x;
+                    transform.translation.x -= step.x * 0.5;
+                }
+                if back_y || corner {
+                    velocity.0.y = -velocity.0.y;
+                    transform.translation.y -= step.y * 0.5;
+                }
+            }
+            WallBehavior::PassThrough => {}
+        }
+    }
+}
+
 fn handle_projectile_collisions(
     mut commands: Commands,
     mut projectile_q: Query<(Entity, &Transform, &mut Projectile, &Collider)>,
-    mut enemy_q: Query<(Entity, &Transform, &mut Health, &Collider), With<Enemy>>,
+    enemy_q: Query<(Entity, &Transform, &Collider), With<Enemy>>,
+    mut combat_events: EventWriter<CombatEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
 ) {
     for (proj_entity, proj_tf, mut projectile, proj_collider) in projectile_q.iter_mut() {
-        for (enemy_entity, enemy_tf, mut enemy_health, enemy_collider) in enemy_q.iter_mut() {
+        for (enemy_entity, enemy_tf, enemy_collider) in enemy_q.iter() {
             if projectile.hit_entities.contains(&enemy_entity) {
                 continue;
             }
@@ -303,7 +352,26 @@ fn handle_projectile_collisions(
             let collision_dist = (proj_collider.size.x + enemy_collider.size.x) / 2.0;
 
             if distance <= collision_dist {
-                enemy_health.take_damage(projectile.damage);
+                combat_events.write(CombatEvent {
+                    attacker: projectile.owner,
+                    target: enemy_entity,
+                    damage: projectile.damage,
+                    damage_type: projectile.damage_type,
+                    position: proj_tf.translation,
+                    kind: HitKind::Direct,
+                });
+                for kind in &projectile.on_hit {
+                    status_events.write(ApplyStatusEvent {
+                        target: enemy_entity,
+                        source: projectile.owner,
+                        kind: *kind,
+                        duration: ADDED_EFFECT_DURATION,
+                        stacks: 1,
+                    });
+                }
+                if projectile.burn_ground {
+                    spawn_burn_patch(&mut commands, enemy_tf.translation, projectile.owner);
+                }
                 projectile.hit_entities.push(enemy_entity);
                 
                 // Spawn impact effect
@@ -0,0 +1,319 @@
+use bevy::prelude::*;
+use crate::core::events::{CombatEvent, HitKind};
+use crate::game::combat::{Health, CombatStats, DamageShield};
+use crate::game::enemy::Enemy;
+use crate::game::movement::{Velocity, Collider};
+use super::*;
+use super::projectile_system::{Projectile, find_nearest_enemy};
+
+const TURRET_RANGE: f32 = 300.0;
+const TURRET_FIRE_RATE: f32 = 0.8;
+const TURRET_BOLT_SPEED: f32 = 350.0;
+const TURRET_SPACING: f32 = 40.0;
+const ORB_RADIUS: f32 = 48.0;
+const ORB_SPEED: f32 = 3.0;          // Radians per second
+const SHIELD_RADIUS: f32 = 30.0;
+const SHIELD_SPEED: f32 = 2.0;
+const SHIELD_ABSORB: i32 = 20;       // Damage each shield soaks before it breaks
+const CONTACT_RANGE: f32 = 18.0;
+const CONTACT_RATE: f32 = 0.5;       // Seconds between contact hits on the same orb or shield
+const MINION_HEALTH: i32 = 30;
+const MINION_SPEED: f32 = 150.0;
+const MINION_SIGHT: f32 = 250.0;
+const MINION_LEASH: f32 = 60.0;      // Idle minions stay this close to their owner
+const MINION_ATTACK_RATE: f32 = 0.8;
+
+pub struct SummonPlugin;
+
+impl Plugin for SummonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            spawn_summons,
+            expire_summons,
+            orbit_summons,
+            summon_contact_damage,
+            fire_turrets,
+            update_minions,
+            break_spent_shields,
+        ).chain());
+    }
+}
+
+/// Anything an ability conjures to fight alongside its caster
+#[derive(Component)]
+pub struct Summon {
+    pub summon_type: SummonType,
+    pub ability_id: AbilityId, // Recasting replaces this ability's previous summons
+    pub owner: Entity,
+    pub damage: i32,
+    pub lifetime: Timer,
+    pub attack_timer: Timer,
+    pub orbit_angle: f32,
+}
+
+fn spawn_summons(
+    mut commands: Commands,
+    mut events: EventReader<TriggerAbilityEvent>,
+    registry: Res<AbilityRegistry>,
+    summon_q: Query<(Entity, &Summon)>,
+) {
+    for event in events.read() {
+        let Some(definition) = registry.abilities.get(&event.ability_id) else { continue };
+        let AbilityType::Summon(ref config) = definition.ability_type else { continue };
+
+        for (entity, summon) in summon_q.iter() {
+            if summon.ability_id == event.ability_id && summon.owner == event.caster {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        let count = (config.count + event.bonuses.extra_projectiles).max(1);
+        let duration = config.duration + event.bonuses.duration;
+        for index in 0..count {
+            let angle = index as f32 * std::f32::consts::TAU / count as f32;
+            let attack_rate = match config.summon_type {
+                SummonType::Turret => TURRET_FIRE_RATE,
+                SummonType::Minion => MINION_ATTACK_RATE,
+                SummonType::Orb | SummonType::Shield => CONTACT_RATE,
+            };
+            let summon = Summon {
+                summon_type: config.summon_type,
+                ability_id: event.ability_id,
+                owner: event.caster,
+                damage: config.damage + event.bonuses.damage,
+                lifetime: Timer::from_seconds(duration, TimerMode::Once),
+                attack_timer: Timer::from_seconds(attack_rate, TimerMode::Repeating),
+                orbit_angle: angle,
+            };
+
+            // Turrets and minions start spread around the caster
+            let spread = if count > 1 { Vec2::from_angle(angle) * TURRET_SPACING } else { Vec2::ZERO };
+            let position = event.position.truncate() + spread;
+            let (color, size) = match config.summon_type {
+                SummonType::Turret => (Color::srgb(0.55, 0.35, 0.15), Vec2::splat(16.0)),
+                SummonType::Orb => (Color::srgb(0.4, 0.9, 1.0), Vec2::splat(10.0)),
+                SummonType::Shield => (Color::srgb(1.0, 0.85, 0.3), Vec2::new(14.0, 6.0)),
+                SummonType::Minion => (Color::srgb(0.4, 0.8, 0.3), Vec2::splat(14.0)),
+            };
+            let mut entity = commands.spawn((
+                Sprite {
+                    color,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                Transform::from_translation(position.extend(event.position.z + 1.0)),
+            ));
+            if config.summon_type == SummonType::Minion {
+                entity.insert((
+                    Health::new(MINION_HEALTH),
+                    Velocity(Vec2::ZERO),
+                    Collider { size },
+                ));
+            }
+            entity.insert(summon);
+        }
+
+        // The shields' combined strength lives on the caster so every hit drains it
+        if config.summon_type == SummonType::Shield {
+            commands.entity(event.caster).insert(DamageShield {
+                amount: SHIELD_ABSORB * count as i32,
+                timer: Timer::from_seconds(duration, TimerMode::Once),
+            });
+        }
+    }
+}
+
+/// Removes summons that ran out of time, died, or lost their caster
+fn expire_summons(
+    mut commands: Commands,
+    mut summon_q: Query<(Entity, &mut Summon, Option<&Health>)>,
+    owner_q: Query<(), With<Transform>>,
+    time: Res<Time>,
+) {
+    for (entity, mut summon, health) in summon_q.iter_mut() {
+        summon.lifetime.tick(time.delta());
+        if summon.lifetime.finished() || health.is_some_and(Health::is_dead) || !owner_q.contains(summon.owner) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn orbit_summons(
+    mut summon_q: Query<(&mut Summon, &mut Transform)>,
+    owner_q: Query<&Transform, Without<Summon>>,
+    time: Res<Time>,
+) {
+    for (mut summon, mut transform) in summon_q.iter_mut() {
+        let (radius, speed) = match summon.summon_type {
+            SummonType::Orb => (ORB_RADIUS, ORB_SPEED),
+            SummonType::Shield => (SHIELD_RADIUS, SHIELD_SPEED),
+            _ => continue,
+        };
+        let Ok(owner_tf) = owner_q.get(summon.owner) else { continue };
+
+        summon.orbit_angle += speed * time.delta_secs();
+        let offset = Vec2::from_angle(summon.orbit_angle) * radius;
+        transform.translation = owner_tf.translation + offset.extend(1.0);
+        transform.rotation = Quat::from_rotation_z(summon.orbit_angle);
+    }
+}
+
+/// Orbs and shields hurt every enemy they pass through
+fn summon_contact_damage(
+    mut summon_q: Query<(&mut Summon, &Transform)>,
+    enemy_q: Query<(Entity, &Transform), (With<Enemy>, Without<Summon>)>,
+    mut combat_events: EventWriter<CombatEvent>,
+    time: Res<Time>,
+) {
+    for (mut summon, transform) in summon_q.iter_mut() {
+        if !matches!(summon.summon_type, SummonType::Orb | SummonType::Shield) {
+            continue;
+        }
+        summon.attack_timer.tick(time.delta());
+        if !summon.attack_timer.just_finished() {
+            continue;
+        }
+
+        for (enemy_entity, enemy_tf) in enemy_q.iter() {
+            if enemy_tf.translation.truncate().distance(transform.translation.truncate()) <= CONTACT_RANGE {
+                combat_events.write(CombatEvent {
+                    attacker: summon.owner,
+                    target: enemy_entity,
+                    damage: summon.damage,
+                    damage_type: DamageType::Physical,
+                    position: enemy_tf.translation,
+                    kind: HitKind::Direct,
+                });
+            }
+        }
+    }
+}
+
+fn fire_turrets(
+    mut commands: Commands,
+    mut summon_q: Query<(&mut Summon, &Transform)>,
+    enemy_q: Query<&Transform, (With<Enemy>, Without<Projectile>)>,
+    time: Res<Time>,
+) {
+    for (mut summon, transform) in summon_q.iter_mut() {
+        if summon.summon_type != SummonType::Turret {
+            continue;
+        }
+        summon.attack_timer.tick(time.delta());
+        if !summon.attack_timer.just_finished() {
+            continue;
+        }
+
+        let Some(target) = find_nearest_enemy(&transform.translation, &enemy_q) else { continue };
+        if target.distance(transform.translation) > TURRET_RANGE {
+            continue;
+        }
+        let direction = (target - transform.translation).truncate().normalize_or_zero();
+        commands.spawn((
+            Projectile {
+                damage: summon.damage,
+                damage_type: DamageType::Physical,
+                pierce_remaining: 0,
+                lifetime: Timer::from_seconds(TURRET_RANGE / TURRET_BOLT_SPEED, TimerMode::Once),
+                owner: summon.owner,
+                hit_entities: Vec::new(),
+                wall_behavior: WallBehavior::Stop,
+                on_hit: Vec::new(),
+                burn_ground: false,
+            },
+            Velocity(direction * TURRET_BOLT_SPEED),
+            Collider { size: Vec2::splat(6.0) },
+            Sprite {
+                color: Color::srgb(1.0, 0.8, 0.4),
+                custom_size: Some(Vec2::splat(6.0)),
+                ..default()
+            },
+            Transform::from_translation(transform.translation + Vec3::new(0.0, 0.0, 1.0)),
+        ));
+    }
+}
+
+/// Minions chase the nearest enemy in sight and trade blows with it, otherwise they follow their owner
+fn update_minions(
+    mut summon_q: Query<(Entity, &mut Summon, &Transform, &mut Velocity)>,
+    enemy_q: Query<(Entity, &Transform, &CombatStats), (With<Enemy>, Without<Summon>)>,
+    owner_q: Query<&Transform, Without<Summon>>,
+    mut combat_events: EventWriter<CombatEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut summon, transform, mut velocity) in summon_q.iter_mut() {
+        if summon.summon_type != SummonType::Minion {
+            continue;
+        }
+        summon.attack_timer.tick(time.delta());
+        let position = transform.translation.truncate();
+
+        let target = enemy_q.iter()
+            .map(|(enemy, enemy_tf, stats)| (enemy, enemy_tf.translation.truncate(), stats))
+            .filter(|(_, enemy_pos, _)| enemy_pos.distance(position) <= MINION_SIGHT)
+            .min_by(|(_, a, _), (_, b, _)| a.distance(position).total_cmp(&b.distance(position)));
+
+        let Some((enemy, enemy_pos, stats)) = target else {
+            // Nothing to fight, stay near the owner
+            velocity.0 = match owner_q.get(summon.owner) {
+                Ok(owner_tf) if owner_tf.translation.truncate().distance(position) > MINION_LEASH => {
+                    (owner_tf.translation.truncate() - position).normalize_or_zero() * MINION_SPEED
+                }
+                _ => Vec2::ZERO,
+            };
+            continue;
+        };
+
+        if enemy_pos.distance(position) > CONTACT_RANGE {
+            velocity.0 = (enemy_pos - position).normalize_or_zero() * MINION_SPEED;
+            continue;
+        }
+
+        velocity.0 = Vec2::ZERO;
+        if summon.attack_timer.just_finished() {
+            combat_events.write(CombatEvent {
+                attacker: summon.owner,
+                target: enemy,
+                damage: summon.damage,
+                damage_type: DamageType::Physical,
+                position: enemy_pos.extend(transform.translation.z),
+                kind: HitKind::Direct,
+            });
+            combat_events.write(CombatEvent {
+                attacker: enemy,
+                target: entity,
+                damage: stats.damage,
+                damage_type: DamageType::Physical,
+                position: transform.translation,
+                kind: HitKind::Direct,
+            });
+        }
+    }
+}
+
+/// Shield summons shatter one by one as the caster's DamageShield is drained
+fn break_spent_shields(
+    mut commands: Commands,
+    summon_q: Query<(Entity, &Summon)>,
+    owner_q: Query<Option<&DamageShield>>,
+) {
+    for (entity, summon) in summon_q.iter() {
+        if summon.summon_type != SummonType::Shield {
+            continue;
+        }
+        let remaining = owner_q.get(summon.owner).ok().flatten().map_or(0, |shield| shield.amount.max(0));
+        let intact = (remaining + SHIELD_ABSORB - 1) / SHIELD_ABSORB;
+        let shields = summon_q.iter()
+            .filter(|(_, other)| other.summon_type == SummonType::Shield && other.owner == summon.owner)
+            .count() as i32;
+        // Break the shields with the lowest entity ids first so the choice is stable
+        let rank = summon_q.iter()
+            .filter(|(other_entity, other)| {
+                other.summon_type == SummonType::Shield && other.owner == summon.owner && *other_entity < entity
+            })
+            .count() as i32;
+        if rank < shields - intact {
+            commands.entity(entity).despawn();
+        }
+    }
+}
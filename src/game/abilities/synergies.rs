@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+use crate::core::save_system::SaveData;
+use crate::entities::powerup::PowerUpSlots;
+use crate::systems::talents::AbilityModification;
+use super::*;
+
+const SET_SIZE: u32 = 3; // Copies of one fruit that unlock its ultimate
+
+pub const FRUIT_NAMES: [&str; 7] = ["Strawberry", "Pear", "Mango", "Pineapple", "Apple", "Carrot", "Coconut"];
+
+pub struct SynergyPlugin;
+
+impl Plugin for SynergyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SynergyTable>()
+            .add_systems(Update, update_synergies);
+    }
+}
+
+/// What a fruit pair adds to abilities while both fruits are slotted
+#[derive(Clone)]
+pub enum CrossEffect {
+    Modify(&'static str, AbilityModification), // Targets abilities the same way talents do
+    BurnGround,                                // Projectile hits leave burning ground
+}
+
+pub struct PairSynergy {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub fruits: (u8, u8),
+    pub effect: CrossEffect,
+}
+
+/// Every fruit pair synergy. Three copies of one fruit also unlock that fruit's ultimate.
+#[derive(Resource)]
+pub struct SynergyTable {
+    pub pairs: Vec<PairSynergy>,
+}
+
+impl Default for SynergyTable {
+    fn default() -> Self {
+        Self {
+            pairs: vec![
+                PairSynergy {
+                    id: "jam_fire",
+                    name: "Jam Fire",
+                    description: "Projectile hits leave burning ground",
+                    fruits: (0, 2),
+                    effect: CrossEffect::BurnGround,
+                },
+                PairSynergy {
+                    id: "fruit_salvo",
+                    name: "Fruit Salvo",
+                    description: "Projectile abilities fire an extra shot",
+                    fruits: (0, 1),
+                    effect: CrossEffect::Modify("projectile", AbilityModification::ExtraProjectiles(1)),
+                },
+                PairSynergy {
+                    id: "cold_press",
+                    name: "Cold Press",
+                    description: "Projectiles slow what they hit",
+                    fruits: (1, 6),
+                    effect: CrossEffect::Modify("projectile", AbilityModification::AddEffect("slow".to_string())),
+                },
+                PairSynergy {
+                    id: "tropical_heat",
+                    name: "Tropical Heat",
+                    description: "Areas are 25% larger",
+                    fruits: (2, 3),
+                    effect: CrossEffect::Modify("area", AbilityModification::IncreaseRadius(0.25)),
+                },
+                PairSynergy {
+                    id: "roasted_roots",
+                    name: "Roasted Roots",
+                    description: "Areas set enemies on fire",
+                    fruits: (2, 5),
+                    effect: CrossEffect::Modify("area", AbilityModification::AddEffect("burn".to_string())),
+                },
+                PairSynergy {
+                    id: "spiked_husk",
+                    name: "Spiked Husk",
+                    description: "Projectiles poison what they hit",
+                    fruits: (3, 6),
+                    effect: CrossEffect::Modify("projectile", AbilityModification::AddEffect("poison".to_string())),
+                },
+                PairSynergy {
+                    id: "deep_roots",
+                    name: "Deep Roots",
+                    description: "All abilities recharge 15% faster",
+                    fruits: (4, 5),
+                    effect: CrossEffect::Modify("all", AbilityModification::ReduceCooldown(0.15)),
+                },
+            ],
+        }
+    }
+}
+
+impl SynergyTable {
+    /// Pair synergies plus one full set per fruit
+    pub fn total(&self) -> usize {
+        self.pairs.len() + FRUIT_NAMES.len()
+    }
+}
+
+/// Records a synergy the first time it's assembled
+fn discover(save_data: &mut SaveData, id: &str, name: &str) {
+    if !save_data.discovered_synergies.iter().any(|found| found == id) {
+        println!("Synergy discovered: {}!", name);
+        save_data.discovered_synergies.push(id.to_string());
+    }
+}
+
+/// Synergies from the player's current fruits
+#[derive(Component, Default)]
+pub struct ActiveSynergies {
+    pub full_set: Option<u8>,
+    pub pairs: Vec<usize>, // Indices into SynergyTable::pairs
+}
+
+/// The newest fruit with three copies. Duplicate pickups level a slotted fruit instead of
+/// filling another slot, so each level counts as one more copy.
+pub fn full_set(slots: &PowerUpSlots) -> Option<u8> {
+    let copies = |fruit: u8| -> u32 {
+        slots.slots.iter().filter(|slot| slot.fruit_type == fruit).map(|slot| slot.level).sum()
+    };
+    slots.slots.iter().map(|slot| slot.fruit_type).find(|&fruit| copies(fruit) >= SET_SIZE)
+}
+
+pub fn set_id(fruit_type: u8) -> String {
+    format!("set_{}", FRUIT_NAMES.get(fruit_type as usize).unwrap_or(&"unknown").to_lowercase())
+}
+
+fn update_synergies(
+    mut commands: Commands,
+    player_q: Query<(Entity, &PowerUpSlots), (With<Player>, Changed<PowerUpSlots>)>,
+    table: Res<SynergyTable>,
+    registry: Res<AbilityRegistry>,
+    mut save_data: ResMut<SaveData>,
+    mut modifiers: ResMut<AbilityModifiers>,
+) {
+    for (entity, slots) in player_q.iter() {
+        let fruits: Vec<u8> = slots.slots.iter().map(|slot| slot.fruit_type).collect();
+        let mut active = ActiveSynergies {
+            full_set: full_set(slots),
+            pairs: Vec::new(),
+        };
+
+        if let Some(fruit) = active.full_set {
+            let ultimate = registry.abilities.get(&AbilityId { fruit_type: fruit, body_part: BodyPart::Ultimate });
+            discover(&mut save_data, &set_id(fruit), ultimate.map_or("Fruit set", |definition| definition.name.as_str()));
+        }
+
+        // Pairs apply while both of their fruits are slotted, in any order
+        modifiers.synergy_modifications.clear();
+        modifiers.projectile_burn_ground = false;
+        for (index, pair) in table.pairs.iter().enumerate() {
+            let (a, b) = pair.fruits;
+            if !fruits.contains(&a) || !fruits.contains(&b) {
+                continue;
+            }
+            active.pairs.push(index);
+            discover(&mut save_data, pair.id, pair.name);
+            match &pair.effect {
+                CrossEffect::Modify(target, modification) => {
+                    modifiers.synergy_modifications.push((target.to_string(), modification.clone()));
+                }
+                CrossEffect::BurnGround => modifiers.projectile_burn_ground = true,
+            }
+        }
+
+        commands.entity(entity).insert(active);
+    }
+}
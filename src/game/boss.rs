@@ -0,0 +1,360 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::core::events::{CombatEvent, HitKind, DamageType};
+use crate::game::combat::{Health, CombatStats, DamageImmunity};
+use crate::game::enemy::{Enemy, EnemyType, AIState, Boss, SpawnEnemyEvent, EnemyModifiers};
+use crate::game::enemy_archetypes::{spawn_enemy_projectile, EnemyProjectileKind};
+use crate::game::player::Player;
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                update_boss_phases,
+                update_boss_enrage,
+                run_boss_patterns,
+                update_hazard_zones,
+            ));
+    }
+}
+
+/// One step of a boss's attack rotation
+#[derive(Clone, Copy)]
+pub enum BossPattern {
+    /// Bullets in every direction, rotated a little each time so rings interleave
+    Ring { count: u32, kind: EnemyProjectileKind },
+    /// Fan of bullets aimed at the player, `arc` radians wide
+    Spread { count: u32, arc: f32, kind: EnemyProjectileKind },
+    Summon { enemy_id: &'static str, count: u32 },
+    /// Damaging zones dropped around the player after a short warning
+    Hazards { count: u32, radius: f32, damage: i32, duration: f32 },
+}
+
+/// A boss enters a phase once its health falls to `health_threshold`
+pub struct BossPhase {
+    pub health_threshold: f32,
+    pub pattern_interval: f32,
+    pub speed_multiplier: f32, // Applied on entering the phase
+    pub transition_time: f32,  // Seconds of invulnerability on entering the phase
+    pub patterns: &'static [BossPattern],
+}
+
+/// Damaging floor zone left by a boss's area denial pattern
+#[derive(Component)]
+pub struct HazardZone {
+    pub owner: Entity,
+    pub radius: f32,
+    pub damage: i32,
+    pub warmup: Timer,
+    pub lifetime: Timer,
+    pub tick_timer: Timer,
+}
+
+const RING_ROTATION: f32 = 0.3;
+const HAZARD_SCATTER: f32 = 120.0;
+const ENRAGE_SPEED: f32 = 1.5;
+const ENRAGE_DAMAGE: f32 = 1.5;
+const ENRAGE_PATTERN_RATE: f32 = 0.5; // Enraged bosses fire patterns twice as often
+
+const GOBLIN_KING_PHASES: &[BossPhase] = &[
+    BossPhase {
+        health_threshold: 1.0,
+        pattern_interval: 3.0,
+        speed_multiplier: 1.0,
+        transition_time: 0.0,
+        patterns: &[
+            BossPattern::Spread { count: 5, arc: 0.8, kind: EnemyProjectileKind::Arrow },
+            BossPattern::Summon { enemy_id: "goblin", count: 2 },
+        ],
+    },
+    BossPhase {
+        health_threshold: 0.6,
+        pattern_interval: 2.5,
+        speed_multiplier: 1.2,
+        transition_time: 1.5,
+        patterns: &[
+            BossPattern::Spread { count: 7, arc: 1.0, kind: EnemyProjectileKind::Arrow },
+            BossPattern::Summon { enemy_id: "goblin", count: 3 },
+            BossPattern::Ring { count: 12, kind: EnemyProjectileKind::Arrow },
+        ],
+    },
+    BossPhase {
+        health_threshold: 0.3,
+        pattern_interval: 2.0,
+        speed_multiplier: 1.25,
+        transition_time: 2.0,
+        patterns: &[
+            BossPattern::Ring { count: 16, kind: EnemyProjectileKind::Arrow },
+            BossPattern::Summon { enemy_id: "orc", count: 1 },
+            BossPattern::Spread { count: 9, arc: 1.2, kind: EnemyProjectileKind::Arrow },
+        ],
+    },
+];
+
+const LICH_LORD_PHASES: &[BossPhase] = &[
+    BossPhase {
+        health_threshold: 1.0,
+        pattern_interval: 3.0,
+        speed_multiplier: 1.0,
+        transition_time: 0.0,
+        patterns: &[
+            BossPattern::Ring { count: 10, kind: EnemyProjectileKind::DarkBolt },
+            BossPattern::Hazards { count: 3, radius: 48.0, damage: 8, duration: 4.0 },
+        ],
+    },
+    BossPhase {
+        health_threshold: 0.6,
+        pattern_interval: 2.5,
+        speed_multiplier: 1.0,
+        transition_time: 2.0,
+        patterns: &[
+            BossPattern::Ring { count: 14, kind: EnemyProjectileKind::DarkBolt },
+            BossPattern::Summon { enemy_id: "skeleton", count: 3 },
+            BossPattern::Hazards { count: 4, radius: 48.0, damage: 10, duration: 4.0 },
+        ],
+    },
+    BossPhase {
+        health_threshold: 0.25,
+        pattern_interval: 1.8,
+        speed_multiplier: 1.3,
+        transition_time: 2.5,
+        patterns: &[
+            BossPattern::Ring { count: 20, kind: EnemyProjectileKind::DarkBolt },
+            BossPattern::Hazards { count: 6, radius: 56.0, damage: 12, duration: 5.0 },
+            BossPattern::Summon { enemy_id: "skeleton", count: 2 },
+            BossPattern::Spread { count: 5, arc: 0.6, kind: EnemyProjectileKind::DarkBolt },
+        ],
+    },
+];
+
+const DRAGON_KNIGHT_PHASES: &[BossPhase] = &[
+    BossPhase {
+        health_threshold: 1.0,
+        pattern_interval: 3.5,
+        speed_multiplier: 1.0,
+        transition_time: 0.0,
+        patterns: &[
+            BossPattern::Spread { count: 3, arc: 0.4, kind: EnemyProjectileKind::Fireball },
+            BossPattern::Hazards { count: 2, radius: 64.0, damage: 12, duration: 5.0 },
+        ],
+    },
+    BossPhase {
+        health_threshold: 0.65,
+        pattern_interval: 3.0,
+        speed_multiplier: 1.2,
+        transition_time: 2.0,
+        patterns: &[
+            BossPattern::Spread { count: 5, arc: 0.7, kind: EnemyProjectileKind::Fireball },
+            BossPattern::Ring { count: 12, kind: EnemyProjectileKind::Fireball },
+            BossPattern::Hazards { count: 3, radius: 64.0, damage: 14, duration: 5.0 },
+        ],
+    },
+    BossPhase {
+        health_threshold: 0.3,
+        pattern_interval: 2.2,
+        speed_multiplier: 1.3,
+        transition_time: 2.5,
+        patterns: &[
+            BossPattern::Ring { count: 18, kind: EnemyProjectileKind::Fireball },
+            BossPattern::Hazards { count: 5, radius: 72.0, damage: 16, duration: 6.0 },
+            BossPattern::Summon { enemy_id: "dark_knight", count: 1 },
+            BossPattern::Spread { count: 7, arc: 1.0, kind: EnemyProjectileKind::Fireball },
+        ],
+    },
+];
+
+/// Phases in order; the first one is active from the start of the fight
+pub fn phase_table(boss_type: EnemyType) -> &'static [BossPhase] {
+    match boss_type {
+        EnemyType::LichLord => LICH_LORD_PHASES,
+        EnemyType::DragonKnight => DRAGON_KNIGHT_PHASES,
+        _ => GOBLIN_KING_PHASES,
+    }
+}
+
+/// Seconds of fighting before the boss enrages
+pub fn enrage_time(boss_type: EnemyType) -> f32 {
+    match boss_type {
+        EnemyType::GoblinKing => 90.0,
+        EnemyType::LichLord => 120.0,
+        EnemyType::DragonKnight => 150.0,
+        _ => 90.0,
+    }
+}
+
+fn update_boss_phases(
+    mut commands: Commands,
+    mut boss_q: Query<(Entity, &mut Boss, &mut Enemy, &Health)>,
+) {
+    for (entity, mut boss, mut enemy, health) in boss_q.iter_mut() {
+        // Phases are 1-based, so the next phase sits at index `phase`
+        let Some(next) = phase_table(enemy.enemy_type).get(boss.phase as usize) else { continue };
+        if health.percentage() > next.health_threshold {
+            continue;
+        }
+
+        boss.phase += 1;
+        boss.pattern_index = 0;
+        boss.pattern_timer = Timer::from_seconds(next.pattern_interval + next.transition_time, TimerMode::Once);
+        enemy.move_speed *= next.speed_multiplier;
+
+        // The boss holds still and shrugs off damage while it transitions
+        enemy.set_state(AIState::Stunned, next.transition_time);
+        commands.entity(entity).insert(DamageImmunity {
+            timer: Timer::from_seconds(next.transition_time, TimerMode::Once),
+        });
+        println!("{} enters phase {}", enemy.enemy_type.display_name(), boss.phase);
+    }
+}
+
+fn update_boss_enrage(
+    mut boss_q: Query<(&mut Boss, &mut Enemy, &mut CombatStats, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (mut boss, mut enemy, mut stats, mut sprite) in boss_q.iter_mut() {
+        if boss.enraged {
+            continue;
+        }
+
+        boss.enrage_timer.tick(time.delta());
+        if boss.enrage_timer.finished() {
+            boss.enraged = true;
+            enemy.move_speed *= ENRAGE_SPEED;
+            stats.damage = (stats.damage as f32 * ENRAGE_DAMAGE) as i32;
+            sprite.color = Color::linear_rgb(1.0, 0.15, 0.15);
+            println!("{} is enraged!", enemy.enemy_type.display_name());
+        }
+    }
+}
+
+fn run_boss_patterns(
+    mut commands: Commands,
+    mut boss_q: Query<(Entity, &mut Boss, &Enemy, &Transform, &CombatStats, Has<DamageImmunity>)>,
+    player_q: Query<&Transform, (With<Player>, Without<Boss>)>,
+    mut spawn_events: EventWriter<SpawnEnemyEvent>,
+    time: Res<Time>,
+) {
+    let Ok(player_tf) = player_q.single() else { return };
+    let player_pos = player_tf.translation.truncate();
+    let mut rng = rand::thread_rng();
+
+    for (entity, mut boss, enemy, transform, stats, immune) in boss_q.iter_mut() {
+        let origin = transform.translation.truncate();
+        if immune || origin.distance(player_pos) > enemy.detection_range {
+            continue;
+        }
+
+        let rate = if boss.enraged { 1.0 / ENRAGE_PATTERN_RATE } else { 1.0 };
+        boss.pattern_timer.tick(time.delta().mul_f32(rate));
+        if !boss.pattern_timer.finished() {
+            continue;
+        }
+
+        let phase = &phase_table(enemy.enemy_type)[boss.phase as usize - 1];
+        let pattern = phase.patterns[boss.pattern_index % phase.patterns.len()];
+        let aim = (player_pos - origin).normalize_or(Vec2::X);
+        let damage = (stats.damage / 2).max(1);
+
+        match pattern {
+            BossPattern::Ring { count, kind } => {
+                let offset = boss.pattern_index as f32 * RING_ROTATION;
+                for i in 0..count {
+                    let angle = offset + i as f32 / count as f32 * std::f32::consts::TAU;
+                    spawn_enemy_projectile(&mut commands, kind, origin, Vec2::from_angle(angle), damage, entity);
+                }
+            }
+            BossPattern::Spread { count, arc, kind } => {
+                let step = if count > 1 { arc / (count - 1) as f32 } else { 0.0 };
+                for i in 0..count {
+                    let angle = -arc / 2.0 + step * i as f32;
+                    spawn_enemy_projectile(&mut commands, kind, origin, Vec2::from_angle(angle).rotate(aim), damage, entity);
+                }
+            }
+            BossPattern::Summon { enemy_id, count } => {
+                for i in 0..count {
+                    let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+                    spawn_events.write(SpawnEnemyEvent {
+                        position: (origin + Vec2::from_angle(angle) * 60.0).extend(3.0),
+                        enemy_id: enemy_id.to_string(),
+                        modifiers: EnemyModifiers::default(),
+                    });
+                }
+            }
+            BossPattern::Hazards { count, radius, damage, duration } => {
+                for _ in 0..count {
+                    let offset = Vec2::new(
+                        rng.gen_range(-HAZARD_SCATTER..HAZARD_SCATTER),
+                        rng.gen_range(-HAZARD_SCATTER..HAZARD_SCATTER),
+                    );
+                    spawn_hazard_zone(&mut commands, entity, player_pos + offset, radius, damage, duration);
+                }
+            }
+        }
+
+        boss.pattern_index += 1;
+        boss.pattern_timer = Timer::from_seconds(phase.pattern_interval, TimerMode::Once);
+    }
+}
+
+pub fn spawn_hazard_zone(commands: &mut Commands, owner: Entity, position: Vec2, radius: f32, damage: i32, duration: f32) {
+    commands.spawn((
+        HazardZone {
+            owner,
+            radius,
+            damage,
+            warmup: Timer::from_seconds(0.8, TimerMode::Once),
+            lifetime: Timer::from_seconds(duration, TimerMode::Once),
+            tick_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+        },
+        Sprite {
+            color: Color::srgba(0.8, 0.2, 0.9, 0.15),
+            custom_size: Some(Vec2::splat(radius * 2.0)),
+            ..default()
+        },
+        Transform::from_translation(position.extend(0.8)),
+    ));
+}
+
+fn update_hazard_zones(
+    mut commands: Commands,
+    mut zone_q: Query<(Entity, &mut HazardZone, &Transform, &mut Sprite)>,
+    player_q: Query<(Entity, &Transform), With<Player>>,
+    mut combat_events: EventWriter<CombatEvent>,
+    time: Res<Time>,
+) {
+    let player = player_q.single().ok();
+
+    for (entity, mut zone, transform, mut sprite) in zone_q.iter_mut() {
+        // Zones only hurt once the warning has played out
+        zone.warmup.tick(time.delta());
+        if !zone.warmup.finished() {
+            continue;
+        }
+        sprite.color.set_alpha(0.45);
+
+        zone.lifetime.tick(time.delta());
+        if zone.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        zone.tick_timer.tick(time.delta());
+        if !zone.tick_timer.just_finished() {
+            continue;
+        }
+        if let Some((player_entity, player_tf)) = player {
+            if transform.translation.truncate().distance(player_tf.translation.truncate()) <= zone.radius {
+                combat_events.write(CombatEvent {
+                    attacker: zone.owner,
+                    target: player_entity,
+                    damage: zone.damage,
+                    damage_type: DamageType::Magic,
+                    position: player_tf.translation,
+                    kind: HitKind::Direct,
+                });
+            }
+        }
+    }
+}
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use crate::game::player::{Player, PlayerStats};
 use crate::game::movement::Collider;
 use crate::entities::powerup::PowerUpSlots;
+use crate::game::abilities::AbilityRegistry;
 use crate::systems::shop::PlayerCurrency;
 use crate::systems::achievements::AchievementUnlockedEvent;
 use crate::systems::quests::QuestCompleteEvent;
@@ -43,6 +44,7 @@ fn handle_collectible_pickup(
     mut achievement_events: EventWriter<AchievementUnlockedEvent>,
     mut quest_events: EventWriter<QuestCompleteEvent>,
     player_entity_q: Query<Entity, With<Player>>,
+    registry: Res<AbilityRegistry>,
 ) {
     let Ok((player_tf, mut player_stats)) = player_q.single_mut() else { return };
     let Ok(player_entity) = player_entity_q.get_single() else { return };
@@ -83,8 +85,27 @@ fn handle_collectible_pickup(
                 }
                 CollectibleType::Fruit(fruit_type) => {
                     if let Ok(mut powerup_slots) = powerup_q.single_mut() {
-                        // Just add the fruit by its type - the ability system will handle the rest
-                        if let Some(dropped) = powerup_slots.add_fruit_for_abilities(fruit_type) {
+                        // A fruit that's already slotted levels up that ability, counting toward its ultimate; at max level it's just eaten
+                        let slotted = powerup_slots.slots.iter().any(|slot| slot.fruit_type == fruit_type);
+                        let mut leveled = None;
+                        for index in 0..powerup_slots.slots.len() {
+                            if powerup_slots.slots[index].fruit_type != fruit_type {
+                                continue;
+                            }
+                            leveled = powerup_slots.level_up(index, registry.max_level(fruit_type, index));
+                            if leveled.is_some() {
+                                break;
+                            }
+                        }
+
+                        if let Some(level) = leveled {
+                            println!("Picked up fruit type {}, ability is now level {}", fruit_type, level);
+                        } else if slotted {
+                            println!("Picked up fruit type {}, ability is already max level", fruit_type);
+                        } else if !powerup_slots.can_add_fruit() {
+                            // Leave it on the ground until a slot is unlocked
+                            continue;
+                        } else if let Some(dropped) = powerup_slots.add_fruit_for_abilities(fruit_type) {
                             println!("Picked up fruit type {}, dropped: fruit type {}", fruit_type, dropped);
                         } else {
                             println!("Picked up fruit type {}", fruit_type);
@@ -1,9 +1,175 @@
 use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use crate::core::events::{CombatEvent, DamageDealtEvent, DamageType, HitKind};
+use crate::core::state::GameStats;
+use crate::game::player::Player;
+use crate::systems::combo::ComboEvent;
+use super::{Health, CombatStats, DamageImmunity, DamageShield};
+use super::modifiers::StatModifiers;
 
-pub fn process_damage_events() {
-    // Damage event processing
+const MAX_RESISTANCE: f32 = 0.9;
+const MAX_DODGE: f32 = 0.75;
+
+/// Fraction of each damage type ignored; negative values are weaknesses. True damage ignores these.
+#[derive(Component, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct Resistances {
+    pub physical: f32,
+    pub magic: f32,
+    pub fire: f32,
+    pub ice: f32,
+    pub poison: f32,
+}
+
+impl Resistances {
+    pub fn get(&self, damage_type: DamageType) -> f32 {
+        let resistance = match damage_type {
+            DamageType::Physical => self.physical,
+            DamageType::Magic => self.magic,
+            DamageType::Fire => self.fire,
+            DamageType::Ice => self.ice,
+            DamageType::Poison => self.poison,
+            DamageType::True => 0.0,
+        };
+        resistance.min(MAX_RESISTANCE)
+    }
+}
+
+/// Player bonuses from one source, added on top of the player's CombatStats when damage resolves
+#[derive(Clone, Copy, Default)]
+pub struct DamageBonuses {
+    pub flat_damage: i32,
+    pub damage_percent: f32,
+    pub crit_chance: f32,
+    pub crit_damage: f32,
+    pub armor: i32,
+    pub dodge_chance: f32,
+    pub thorns: f32, // Fraction of damage taken reflected to the attacker
+}
+
+impl DamageBonuses {
+    fn combine(self, other: DamageBonuses) -> DamageBonuses {
+        DamageBonuses {
+            flat_damage: self.flat_damage + other.flat_damage,
+            damage_percent: self.damage_percent + other.damage_percent,
+            crit_chance: self.crit_chance + other.crit_chance,
+            crit_damage: self.crit_damage + other.crit_damage,
+            armor: self.armor + other.armor,
+            dodge_chance: self.dodge_chance + other.dodge_chance,
+            thorns: self.thorns + other.thorns,
+        }
+    }
+}
+
+/// Player damage bonuses, kept up to date by the talent and shop systems
+#[derive(Resource, Default)]
+pub struct DamageModifiers {
+    pub talents: DamageBonuses,
+    pub upgrades: DamageBonuses,
 }
 
-pub fn show_damage_numbers() {
-    // Damage number display
+impl DamageModifiers {
+    pub fn total(&self) -> DamageBonuses {
+        self.talents.combine(self.upgrades)
+    }
+}
+
+/// Resolves every CombatEvent: player bonuses, timed modifiers, crits, resistances, then armor unless it is true damage.
+/// Contact ticks skip player bonuses and crits so they stay at their flat damage.
+/// Targets with DamageImmunity take nothing; a DamageShield soaks what it can first. Landed hits are reported as DamageDealtEvents.
+pub fn process_damage_events(
+    mut combat_events: EventReader<CombatEvent>,
+    mut target_q: Query<(&mut Health, Option<&CombatStats>, Option<&Resistances>, Option<&mut DamageShield>, Option<&StatModifiers>, Has<Player>), Without<DamageImmunity>>,
+    attacker_q: Query<(&CombatStats, Option<&StatModifiers>, Has<Player>)>,
+    transform_q: Query<&Transform>,
+    modifiers: Res<DamageModifiers>,
+    mut game_stats: ResMut<GameStats>,
+    mut dealt_events: EventWriter<DamageDealtEvent>,
+    mut combo_events: EventWriter<ComboEvent>,
+) {
+    let mut rng = rand::thread_rng();
+    let bonuses = modifiers.total();
+
+    for event in combat_events.read() {
+        // Environmental damage has no attacker and can't crit
+        let attacker = attacker_q.get(event.attacker).ok();
+        let attacker_is_player = attacker.is_some_and(|(.., is_player)| is_player);
+        let contact = event.kind == HitKind::Contact;
+
+        let mut damage = event.damage as f32
+            * attacker.and_then(|(_, modifiers, _)| modifiers).map_or(1.0, |modifiers| modifiers.effective.damage_multiplier);
+        let mut crit_chance = if !contact { attacker.map_or(0.0, |(stats, ..)| stats.crit_chance) } else { 0.0 };
+        let mut crit_multiplier = attacker.map_or(1.0, |(stats, ..)| stats.crit_multiplier);
+        if attacker_is_player && !contact {
+            damage = (damage + bonuses.flat_damage as f32) * (1.0 + bonuses.damage_percent);
+            crit_chance += bonuses.crit_chance;
+            crit_multiplier += bonuses.crit_damage;
+        }
+        let critical = rng.gen::<f32>() < crit_chance;
+        if critical {
+            damage *= crit_multiplier;
+        }
+
+        let Ok((mut health, stats, resistances, shield, target_modifiers, is_player)) = target_q.get_mut(event.target) else { continue };
+        if is_player && rng.gen::<f32>() < bonuses.dodge_chance.min(MAX_DODGE) {
+            continue;
+        }
+
+        let armor = match event.damage_type {
+            DamageType::True => 0,
+            _ => stats.map_or(0, |stats| stats.armor)
+                + target_modifiers.map_or(0, |modifiers| modifiers.effective.armor)
+                + if is_player { bonuses.armor } else { 0 },
+        };
+        damage *= 1.0 - resistances.map_or(0.0, |resistances| resistances.get(event.damage_type));
+        let mut amount = (damage.round() as i32 - armor).max(1);
+        if let Some(mut shield) = shield {
+            let absorbed = amount.min(shield.amount.max(0));
+            shield.amount -= absorbed;
+            amount -= absorbed;
+            if amount == 0 {
+                continue;
+            }
+        }
+        health.take_damage(amount);
+
+        if attacker_is_player {
+            game_stats.damage_dealt += amount as u32;
+        }
+        if is_player {
+            game_stats.damage_taken += amount as u32;
+            // Reset combo when player takes damage
+            combo_events.write(ComboEvent::Reset);
+        }
+        dealt_events.write(DamageDealtEvent {
+            attacker: event.attacker,
+            target: event.target,
+            amount,
+            damage_type: event.damage_type,
+            critical,
+            position: event.position,
+            kind: event.kind,
+        });
+
+        // Thorns hit back directly so reflected damage can't bounce forever
+        if is_player && bonuses.thorns > 0.0 {
+            let reflected = (amount as f32 * bonuses.thorns).round() as i32;
+            if reflected > 0 {
+                if let Ok((mut attacker_health, ..)) = target_q.get_mut(event.attacker) {
+                    attacker_health.take_damage(reflected);
+                    game_stats.damage_dealt += reflected as u32;
+                    dealt_events.write(DamageDealtEvent {
+                        attacker: event.target,
+                        target: event.attacker,
+                        amount: reflected,
+                        damage_type: DamageType::Physical,
+                        critical: false,
+                        position: transform_q.get(event.attacker).map_or(event.position, |transform| transform.translation),
+                        kind: HitKind::Reflected,
+                    });
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,199 @@
+use bevy::prelude::*;
+use crate::core::config::GameConfig;
+use crate::core::events::{DamageDealtEvent, DamageType, HealEvent};
+
+const LIFETIME: f32 = 0.8;
+const MERGE_WINDOW: f32 = 0.25;   // Hits on one target this close together share a number
+const RISE_SPEED: f32 = 40.0;
+const FONT_SIZE: f32 = 14.0;
+const CRIT_SCALE: f32 = 1.6;
+const CRIT_SHAKE: f32 = 3.0;
+const POOL_LIMIT: usize = 256;    // Numbers past this are dropped instead of spawned
+
+/// World-space popup for damage or healing. Pooled: hidden and reused instead of despawned.
+#[derive(Component)]
+pub struct DamageNumber {
+    pub target: Entity,
+    pub amount: i32,
+    pub heal: bool,
+    pub critical: bool,
+    pub age: f32,
+    pub last_hit: f32,   // Age when the last merged hit landed
+    pub origin: Vec3,
+    pub active: bool,
+}
+
+/// Hidden damage numbers waiting to be reused
+#[derive(Resource, Default)]
+pub struct DamageNumberPool {
+    pub free: Vec<Entity>,
+    pub total: usize,
+}
+
+struct Popup {
+    target: Entity,
+    amount: i32,
+    heal: bool,
+    critical: bool,
+    color: Color,
+    position: Vec3,
+}
+
+pub fn damage_type_color(damage_type: DamageType) -> Color {
+    match damage_type {
+        DamageType::Physical => Color::WHITE,
+        DamageType::Magic => Color::srgb(0.7, 0.5, 1.0),
+        DamageType::Fire => Color::srgb(1.0, 0.55, 0.1),
+        DamageType::Ice => Color::srgb(0.5, 0.85, 1.0),
+        DamageType::Poison => Color::srgb(0.6, 0.9, 0.2),
+        DamageType::True => Color::srgb(1.0, 0.95, 0.6),
+    }
+}
+
+/// Turns resolved damage and heals into popups, merging rapid hits on one target
+pub fn show_damage_numbers(
+    mut commands: Commands,
+    mut dealt_events: EventReader<DamageDealtEvent>,
+    mut heal_events: EventReader<HealEvent>,
+    mut pool: ResMut<DamageNumberPool>,
+    mut number_q: Query<(&mut DamageNumber, &mut Text2d, &mut TextColor, &mut Visibility)>,
+    config: Res<GameConfig>,
+) {
+    if !config.show_damage_numbers {
+        dealt_events.clear();
+        heal_events.clear();
+        return;
+    }
+
+    let popups = dealt_events.read().map(|event| Popup {
+        target: event.target,
+        amount: event.amount,
+        heal: false,
+        critical: event.critical,
+        color: damage_type_color(event.damage_type),
+        position: event.position,
+    }).chain(heal_events.read().map(|event| Popup {
+        target: event.target,
+        amount: event.amount,
+        heal: true,
+        critical: false,
+        color: Color::srgb(0.3, 1.0, 0.4),
+        position: event.position,
+    }));
+
+    // Hits landing on one target in the same frame always share a number
+    let mut batch: Vec<Popup> = Vec::new();
+    for popup in popups {
+        match batch.iter_mut().find(|queued| queued.target == popup.target && queued.heal == popup.heal) {
+            Some(queued) => {
+                queued.amount += popup.amount;
+                if popup.critical {
+                    queued.critical = true;
+                    queued.color = popup.color;
+                }
+            }
+            None => batch.push(popup),
+        }
+    }
+
+    for popup in batch {
+        // Fold into a number that's still collecting hits on this target
+        let merged = number_q.iter_mut().find(|(number, ..)| {
+            number.active && number.target == popup.target && number.heal == popup.heal && number.age - number.last_hit < MERGE_WINDOW
+        });
+        if let Some((mut number, mut text, mut color, _)) = merged {
+            number.amount += popup.amount;
+            number.critical |= popup.critical;
+            number.last_hit = number.age;
+            text.0 = label(&number);
+            if popup.critical {
+                color.0 = popup.color;
+            }
+            continue;
+        }
+
+        let number = DamageNumber {
+            target: popup.target,
+            amount: popup.amount,
+            heal: popup.heal,
+            critical: popup.critical,
+            age: 0.0,
+            last_hit: 0.0,
+            origin: popup.position.truncate().extend(50.0),
+            active: true,
+        };
+
+        // Reuse a hidden number if there is one
+        if let Some(entity) = pool.free.pop() {
+            if let Ok((mut pooled, mut text, mut color, mut visibility)) = number_q.get_mut(entity) {
+                text.0 = label(&number);
+                color.0 = popup.color;
+                *visibility = Visibility::Visible;
+                *pooled = number;
+                continue;
+            }
+            // Pooled numbers are never despawned, but don't leak the slot if one was
+            pool.total -= 1;
+        }
+        if pool.total >= POOL_LIMIT {
+            continue;
+        }
+
+        pool.total += 1;
+        commands.spawn((
+            Text2d::new(label(&number)),
+            TextFont {
+                font_size: FONT_SIZE,
+                ..default()
+            },
+            TextColor(popup.color),
+            Transform::from_translation(number.origin),
+            Visibility::Visible,
+            number,
+        ));
+    }
+}
+
+fn label(number: &DamageNumber) -> String {
+    match (number.heal, number.critical) {
+        (true, _) => format!("+{}", number.amount),
+        (false, true) => format!("{}!", number.amount),
+        (false, false) => number.amount.to_string(),
+    }
+}
+
+/// Floats, shakes and fades active numbers, returning finished ones to the pool
+pub fn animate_damage_numbers(
+    mut pool: ResMut<DamageNumberPool>,
+    mut number_q: Query<(Entity, &mut DamageNumber, &mut Transform, &mut TextColor, &mut Visibility)>,
+    time: Res<Time>,
+) {
+    for (entity, mut number, mut transform, mut color, mut visibility) in number_q.iter_mut() {
+        if !number.active {
+            continue;
+        }
+        number.age += time.delta_secs();
+
+        // Merged hits keep the number alive a little longer
+        let remaining = LIFETIME - (number.age - number.last_hit);
+        if remaining <= 0.0 {
+            number.active = false;
+            *visibility = Visibility::Hidden;
+            pool.free.push(entity);
+            continue;
+        }
+
+        let shake = if number.critical && number.age < 0.3 {
+            (number.age * 60.0).sin() * CRIT_SHAKE
+        } else {
+            0.0
+        };
+        transform.translation = number.origin + Vec3::new(shake, number.age * RISE_SPEED, 0.0);
+
+        // Crits pop in large and settle; merged hits pulse back up
+        let pop = 1.0 + (0.15 - (number.age - number.last_hit)).max(0.0) * 2.0;
+        let base = if number.critical { CRIT_SCALE } else { 1.0 };
+        transform.scale = Vec3::splat(base * pop);
+        color.0.set_alpha((remaining / (LIFETIME * 0.4)).min(1.0));
+    }
+}
@@ -1,5 +1,275 @@
 use bevy::prelude::*;
+use rand::Rng;
+use crate::core::events::{CombatEvent, HitKind, DamageDealtEvent, DamageType};
+use crate::game::enemy::{Enemy, Boss, AIState};
+use crate::game::player::Player;
+use crate::systems::loot::{CollectedLoot, SpecialEffect};
 
-pub fn update_status_effects() {
-    // Status effects update
+const MAX_BURN_STACKS: u32 = 3;
+const MAX_POISON_STACKS: u32 = 5;
+const SLOW_PER_STACK: f32 = 0.15;
+const SLOW_STACKS_TO_FREEZE: u32 = 4;   // A fourth slow stack freezes the target solid
+const CONTROL_IMMUNITY: f32 = 2.0;      // Seconds after a freeze or stun ends before another can land
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum StatusKind {
+    Burn,
+    Poison,
+    Slow,
+    Freeze,
+    Stun,
+}
+
+impl StatusKind {
+    /// Names used by loot `SpecialEffect::OnHit`
+    pub fn from_name(name: &str) -> Option<StatusKind> {
+        match name {
+            "burn" => Some(StatusKind::Burn),
+            "poison" => Some(StatusKind::Poison),
+            "slow" => Some(StatusKind::Slow),
+            "freeze" => Some(StatusKind::Freeze),
+            "stun" => Some(StatusKind::Stun),
+            _ => None,
+        }
+    }
+
+    /// Icon color on health bars
+    pub fn color(&self) -> Color {
+        match self {
+            StatusKind::Burn => Color::srgb(1.0, 0.45, 0.1),
+            StatusKind::Poison => Color::srgb(0.5, 0.9, 0.2),
+            StatusKind::Slow => Color::srgb(0.5, 0.7, 1.0),
+            StatusKind::Freeze => Color::srgb(0.8, 0.95, 1.0),
+            StatusKind::Stun => Color::srgb(1.0, 0.9, 0.3),
+        }
+    }
+
+    fn max_stacks(&self) -> u32 {
+        match self {
+            StatusKind::Burn => MAX_BURN_STACKS,
+            StatusKind::Poison => MAX_POISON_STACKS,
+            StatusKind::Slow => SLOW_STACKS_TO_FREEZE,
+            StatusKind::Freeze | StatusKind::Stun => 1,
+        }
+    }
+
+    /// Seconds between ticks and damage per stack, for effects that hurt
+    fn tick(&self) -> Option<(f32, i32, DamageType)> {
+        match self {
+            StatusKind::Burn => Some((0.5, 3, DamageType::Fire)),
+            StatusKind::Poison => Some((1.0, 2, DamageType::Poison)),
+            _ => None,
+        }
+    }
+
+    fn is_control(&self) -> bool {
+        matches!(self, StatusKind::Freeze | StatusKind::Stun)
+    }
+}
+
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub stacks: u32,
+    pub duration: Timer,
+    pub tick_timer: Timer,
+    pub source: Entity, // Credited with tick damage
+}
+
+/// Every status currently on an entity, plus windows where it can't be frozen or stunned again
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+    pub immunities: Vec<(StatusKind, Timer)>,
+}
+
+impl StatusEffects {
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.effects.iter().any(|effect| effect.kind == kind)
+    }
+
+    pub fn stacks(&self, kind: StatusKind) -> u32 {
+        self.effects.iter().find(|effect| effect.kind == kind).map_or(0, |effect| effect.stacks)
+    }
+
+    pub fn is_immune(&self, kind: StatusKind) -> bool {
+        self.immunities.iter().any(|(immune, _)| *immune == kind)
+    }
+
+    /// Frozen or stunned targets can't move or attack
+    pub fn is_disabled(&self) -> bool {
+        self.has(StatusKind::Freeze) || self.has(StatusKind::Stun)
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_disabled() {
+            return 0.0;
+        }
+        1.0 - SLOW_PER_STACK * self.stacks(StatusKind::Slow) as f32
+    }
+
+    /// Slowed targets also wind up and recover from attacks more slowly
+    pub fn attack_speed_multiplier(&self) -> f32 {
+        1.0 - SLOW_PER_STACK * self.stacks(StatusKind::Slow) as f32
+    }
+
+    fn remove(&mut self, kind: StatusKind) {
+        self.effects.retain(|effect| effect.kind != kind);
+    }
+}
+
+/// Applies (or stacks) a status on `target`
+#[derive(Event)]
+pub struct ApplyStatusEvent {
+    pub target: Entity,
+    pub source: Entity,
+    pub kind: StatusKind,
+    pub duration: f32,
+    pub stacks: u32,
+}
+
+/// Adds statuses from ApplyStatusEvents, resolving interactions between them
+pub fn apply_status_events(
+    mut status_events: EventReader<ApplyStatusEvent>,
+    mut target_q: Query<(&mut StatusEffects, Option<&mut Enemy>, Has<Boss>)>,
+) {
+    for event in status_events.read() {
+        let Ok((mut statuses, enemy, is_boss)) = target_q.get_mut(event.target) else { continue };
+        // Bosses can be stunned but never frozen solid
+        if (is_boss && event.kind == StatusKind::Freeze) || statuses.is_immune(event.kind) {
+            continue;
+        }
+
+        // Fire and ice cancel out
+        let opposite = match event.kind {
+            StatusKind::Burn => Some(StatusKind::Freeze),
+            StatusKind::Freeze => Some(StatusKind::Burn),
+            _ => None,
+        };
+        if let Some(opposite) = opposite.filter(|opposite| statuses.has(*opposite)) {
+            statuses.remove(opposite);
+            if let Some(mut enemy) = enemy.filter(|_| opposite.is_control()) {
+                sync_enemy_stun(&statuses, &mut enemy);
+            }
+            continue;
+        }
+
+        add_status(&mut statuses, event.kind, event.duration, event.stacks, event.source);
+
+        // Enough slow freezes the target
+        if statuses.stacks(StatusKind::Slow) >= SLOW_STACKS_TO_FREEZE && !statuses.is_immune(StatusKind::Freeze) && !is_boss {
+            statuses.remove(StatusKind::Slow);
+            add_status(&mut statuses, StatusKind::Freeze, event.duration, 1, event.source);
+        }
+
+        // Enemy AI already knows how to sit out a stun
+        if let Some(mut enemy) = enemy {
+            sync_enemy_stun(&statuses, &mut enemy);
+        }
+    }
+}
+
+/// Keeps the AI stunned for as long as the longest control effect lasts
+fn sync_enemy_stun(statuses: &StatusEffects, enemy: &mut Enemy) {
+    let disabled_for = statuses.effects.iter()
+        .filter(|effect| effect.kind.is_control())
+        .map(|effect| effect.duration.remaining_secs())
+        .fold(0.0, f32::max);
+    if disabled_for > 0.0 {
+        enemy.stun(disabled_for);
+    } else if enemy.ai_state == AIState::Stunned {
+        // Nothing holds it any more, so it wakes on its next AI update
+        enemy.set_state(AIState::Stunned, 0.0);
+    }
+}
+
+fn add_status(statuses: &mut StatusEffects, kind: StatusKind, duration: f32, stacks: u32, source: Entity) {
+    match statuses.effects.iter_mut().find(|effect| effect.kind == kind) {
+        Some(effect) => {
+            // Re-applying adds stacks and refreshes the duration
+            effect.stacks = (effect.stacks + stacks).min(kind.max_stacks());
+            if duration > effect.duration.remaining_secs() {
+                effect.duration = Timer::from_seconds(duration, TimerMode::Once);
+            }
+            effect.source = source;
+        }
+        None => {
+            let interval = kind.tick().map_or(1.0, |(interval, ..)| interval);
+            statuses.effects.push(StatusEffect {
+                kind,
+                stacks: stacks.clamp(1, kind.max_stacks()),
+                duration: Timer::from_seconds(duration, TimerMode::Once),
+                tick_timer: Timer::from_seconds(interval, TimerMode::Repeating),
+                source,
+            });
+        }
+    }
+}
+
+/// Ticks damage over time, expires statuses and opens immunity windows after freezes and stuns
+pub fn update_status_effects(
+    mut status_q: Query<(Entity, &mut StatusEffects, &Transform)>,
+    mut combat_events: EventWriter<CombatEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut statuses, transform) in status_q.iter_mut() {
+        let statuses = &mut *statuses;
+
+        for effect in statuses.effects.iter_mut() {
+            effect.duration.tick(time.delta());
+            let Some((_, damage, damage_type)) = effect.kind.tick() else { continue };
+            effect.tick_timer.tick(time.delta());
+            if effect.tick_timer.just_finished() {
+                combat_events.write(CombatEvent {
+                    attacker: effect.source,
+                    target: entity,
+                    damage: damage * effect.stacks as i32,
+                    damage_type,
+                    position: transform.translation,
+                    kind: HitKind::Tick,
+                });
+            }
+        }
+
+        for effect in statuses.effects.iter().filter(|effect| effect.duration.finished() && effect.kind.is_control()) {
+            statuses.immunities.push((effect.kind, Timer::from_seconds(CONTROL_IMMUNITY, TimerMode::Once)));
+        }
+        statuses.effects.retain(|effect| !effect.duration.finished());
+
+        for (_, timer) in statuses.immunities.iter_mut() {
+            timer.tick(time.delta());
+        }
+        statuses.immunities.retain(|(_, timer)| !timer.finished());
+    }
+}
+
+/// Rolls the player's equipment `OnHit` effects against every direct hit they land.
+/// Ticks and contact damage don't proc, so a burn can't keep re-applying itself.
+pub fn apply_on_hit_effects(
+    mut dealt_events: EventReader<DamageDealtEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+    collected: Res<CollectedLoot>,
+    player_q: Query<Entity, With<Player>>,
+) {
+    let Ok(player) = player_q.single() else { return };
+    let mut rng = rand::thread_rng();
+
+    for event in dealt_events.read().filter(|event| event.attacker == player && event.kind == HitKind::Direct) {
+        let on_hit = collected.equipment.iter()
+            .flat_map(|equipment| equipment.special_effects.iter())
+            .filter_map(|effect| match effect {
+                SpecialEffect::OnHit(name, chance) => StatusKind::from_name(name).map(|kind| (kind, *chance)),
+                _ => None,
+            });
+        for (kind, chance) in on_hit {
+            if rng.gen::<f32>() < chance {
+                status_events.write(ApplyStatusEvent {
+                    target: event.target,
+                    source: player,
+                    kind,
+                    duration: 3.0,
+                    stacks: 1,
+                });
+            }
+        }
+    }
 }
@@ -1,11 +1,16 @@
 pub mod damage;
+pub mod damage_numbers;
 pub mod effects;
+pub mod modifiers;
 pub mod projectiles;
 
 use bevy::prelude::*;
 use crate::game::player::Player;
-use crate::game::enemy::Enemy;
+use crate::game::enemy::{Enemy, EnemyRewards};
+use crate::game::elite::Elite;
+use crate::game::abilities::summons::Summon;
 use crate::game::movement::Collider;
+use crate::core::events::{GameEvent, GameEventType, CombatEvent, HitKind, DamageType};
 use crate::systems::loot::{DropLootEvent, LootSource};
 use crate::systems::combo::ComboEvent;
 use crate::systems::achievements::AchievementUnlockedEvent;
@@ -16,14 +21,26 @@ pub struct CombatPlugin;
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_resource::<damage::DamageModifiers>()
+            .init_resource::<damage_numbers::DamageNumberPool>()
+            .add_event::<effects::ApplyStatusEvent>()
+            .add_event::<modifiers::ApplyModifierEvent>()
             .add_systems(Update, (
                 handle_combat,
                 damage::process_damage_events,
-                damage::show_damage_numbers,
-                effects::update_status_effects,
+                damage_numbers::show_damage_numbers.after(damage::process_damage_events),
+                damage_numbers::animate_damage_numbers,
+                effects::apply_status_events,
+                effects::update_status_effects.after(effects::apply_status_events),
+                effects::apply_on_hit_effects.after(damage::process_damage_events),
+                modifiers::apply_modifier_events,
+                modifiers::update_stat_modifiers.after(modifiers::apply_modifier_events),
+                modifiers::apply_life_steal.after(damage::process_damage_events),
                 projectiles::update_projectiles,
                 cleanup_dead_entities,
                 health_regeneration,
+                update_damage_immunity,
+                update_damage_shields,
             ));
     }
 }
@@ -71,11 +88,19 @@ pub struct CombatStats {
     pub crit_multiplier: f32,
 }
 
+/// Ignores all incoming damage until the timer runs out
 #[derive(Component)]
 pub struct DamageImmunity {
     pub timer: Timer,
 }
 
+/// Soaks up incoming damage before it reaches Health, until used up or the timer runs out
+#[derive(Component)]
+pub struct DamageShield {
+    pub amount: i32,
+    pub timer: Timer,
+}
+
 #[derive(Component)]
 pub struct LastDamageTime {
     pub timer: Timer,
@@ -89,14 +114,16 @@ impl Default for LastDamageTime {
     }
 }
 
+/// Contact damage between the player and enemies, sent through the damage pipeline
 pub fn handle_combat(
-    mut player_q: Query<(Entity, &Transform, &mut Health, &CombatStats, &Collider, Option<&mut LastDamageTime>), (With<Player>, Without<Enemy>)>,
-    mut enemy_q: Query<(&Transform, &mut Health, &CombatStats, &Collider), (With<Enemy>, Without<Player>)>,
+    mut player_q: Query<(Entity, &Transform, &Collider, Option<&mut LastDamageTime>), (With<Player>, Without<Enemy>)>,
+    enemy_q: Query<(Entity, &Transform, &CombatStats, &Collider), (With<Enemy>, Without<Player>)>,
     mut commands: Commands,
+    mut combat_events: EventWriter<CombatEvent>,
     mut combo_events: EventWriter<ComboEvent>,
     time: Res<Time>,
 ) {
-    let Ok((player_entity, player_tf, mut player_health, player_stats, player_collider, player_damage_time)) = player_q.single_mut() else { return };
+    let Ok((player_entity, player_tf, player_collider, player_damage_time)) = player_q.single_mut() else { return };
     
     // Update player damage immunity timer
     let mut can_take_damage = true;
@@ -105,7 +132,7 @@ pub fn handle_combat(
         can_take_damage = damage_time.timer.finished();
     }
     
-    for (enemy_tf, mut enemy_health, enemy_stats, enemy_collider) in enemy_q.iter_mut() {
+    for (enemy_entity, enemy_tf, enemy_stats, enemy_collider) in enemy_q.iter() {
         let distance = player_tf.translation.distance(enemy_tf.translation);
         let collision_distance = (player_collider.size.x + enemy_collider.size.x) / 2.0;
         
@@ -113,18 +140,29 @@ pub fn handle_combat(
         if distance <= collision_distance {
             // Enemy damages player
             if can_take_damage {
-                let damage = (enemy_stats.damage - player_stats.armor).max(1);
-                player_health.take_damage(damage);
-                
-                // Reset combo when player takes damage
-                combo_events.send(ComboEvent::Reset);
+                combat_events.write(CombatEvent {
+                    attacker: enemy_entity,
+                    target: player_entity,
+                    damage: enemy_stats.damage,
+                    damage_type: DamageType::Physical,
+                    position: player_tf.translation,
+                    kind: HitKind::Direct,
+                });
                 
                 // Add damage immunity period
                 commands.entity(player_entity).insert(LastDamageTime::default());
+                can_take_damage = false;
             }
             
             // Player damages enemy (continuous damage when touching)
-            enemy_health.take_damage(1);
+            combat_events.write(CombatEvent {
+                attacker: player_entity,
+                target: enemy_entity,
+                damage: 1,
+                damage_type: DamageType::Physical,
+                position: enemy_tf.translation,
+                kind: HitKind::Contact,
+            });
             
             // Send combo event for hit
             combo_events.send(ComboEvent::Hit);
@@ -145,33 +183,63 @@ fn health_regeneration(
     }
 }
 
-fn cleanup_dead_entities(
+pub fn cleanup_dead_entities(
     mut commands: Commands,
-    query: Query<(Entity, &Health, &Transform, Option<&Enemy>)>,
+    // Fallen minions are cleaned up by the summon systems and never count as kills
+    query: Query<(Entity, &Health, &Transform, Option<&Enemy>, Option<&EnemyRewards>, Has<Elite>), Without<Summon>>,
     mut state: ResMut<crate::core::state::GameStats>,
     mut loot_events: EventWriter<DropLootEvent>,
     mut combo_events: EventWriter<ComboEvent>,
     mut achievement_events: EventWriter<AchievementUnlockedEvent>,
     mut quest_events: EventWriter<QuestCompleteEvent>,
-    player_q: Query<Entity, With<Player>>,
+    mut game_events: EventWriter<GameEvent>,
+    mut player_q: Query<(Entity, &mut Player)>,
 ) {
-    let Ok(player_entity) = player_q.get_single() else { return };
+    let Ok((player_entity, mut player)) = player_q.single_mut() else { return };
     
-    for (entity, health, transform, enemy) in query.iter() {
+    for (entity, health, transform, enemy, rewards, is_elite) in query.iter() {
         if health.is_dead() {
             state.enemies_killed += 1;
             state.score += 10;
             
+            if is_elite {
+                state.elites_killed += 1;
+                state.score += 40;
+            }
+            
             // Send combo event for kill
             combo_events.send(ComboEvent::Kill);
             
             // Drop loot if this was an enemy
-            if let Some(_enemy) = enemy {
-                loot_events.send(DropLootEvent {
-                    position: transform.translation,
-                    source: LootSource::Enemy("BasicEnemy".to_string()),
-                    luck_bonus: 0.0, // TODO: Get from player stats
-                });
+            if let Some(enemy) = enemy {
+                // Regular enemies leave a corpse a Necromancer can raise
+                if !enemy.enemy_type.is_boss() {
+                    crate::game::enemy_archetypes::spawn_corpse(&mut commands, transform.translation);
+                }
+                
+                if enemy.enemy_type.is_boss() {
+                    game_events.write(GameEvent {
+                        event_type: GameEventType::BossDefeated(enemy.enemy_type),
+                    });
+                }
+                
+                // Loot table and experience come from the enemy's definition
+                if let Some(rewards) = rewards {
+                    player.experience += rewards.xp;
+                    if let Some(table) = &rewards.loot_table {
+                        let source = if enemy.enemy_type.is_boss() {
+                            LootSource::Boss(table.clone())
+                        } else {
+                            LootSource::Enemy(table.clone())
+                        };
+                        loot_events.send(DropLootEvent {
+                            position: transform.translation,
+                            source,
+                            luck_bonus: 0.0, // TODO: Get from player stats
+                            rolls: rewards.loot_rolls,
+                        });
+                    }
+                }
                 
                 // Trigger achievement progress for enemy kills
                 achievement_events.send(AchievementUnlockedEvent {
@@ -208,3 +276,36 @@ fn cleanup_dead_entities(
         }
     }
 }
+
+fn update_damage_immunity(
+    mut commands: Commands,
+    mut immune_q: Query<(Entity, &mut DamageImmunity, Option<&mut Sprite>)>,
+    time: Res<Time>,
+) {
+    for (entity, mut immunity, sprite) in immune_q.iter_mut() {
+        immunity.timer.tick(time.delta());
+        let finished = immunity.timer.finished();
+        if finished {
+            commands.entity(entity).remove::<DamageImmunity>();
+        }
+
+        // Blink while immune
+        if let Some(mut sprite) = sprite {
+            let visible = finished || (immunity.timer.elapsed_secs() * 10.0) as u32 % 2 == 0;
+            sprite.color.set_alpha(if visible { 1.0 } else { 0.4 });
+        }
+    }
+}
+
+fn update_damage_shields(
+    mut commands: Commands,
+    mut shield_q: Query<(Entity, &mut DamageShield)>,
+    time: Res<Time>,
+) {
+    for (entity, mut shield) in shield_q.iter_mut() {
+        shield.timer.tick(time.delta());
+        if shield.timer.finished() || shield.amount <= 0 {
+            commands.entity(entity).remove::<DamageShield>();
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use bevy::prelude::*;
+use std::mem::discriminant;
+use crate::core::events::{DamageDealtEvent, HealEvent};
+use crate::game::abilities::StatModifier;
+use super::Health;
+
+const MAX_ADDITIVE_STACKS: usize = 5;
+
+/// How a modifier reacts when the same source applies it again
+#[derive(Clone, Copy, PartialEq)]
+pub enum ModifierStacking {
+    Refresh,  // Restart the timer, keeping the stronger value
+    Additive, // Add another copy alongside the existing ones
+}
+
+pub struct TimedModifier {
+    pub modifier: StatModifier,
+    pub source: String,
+    pub timer: Timer,
+}
+
+/// Stats after every active modifier, read by movement and damage resolution
+#[derive(Clone, Copy)]
+pub struct EffectiveStats {
+    pub speed_multiplier: f32,
+    pub damage_multiplier: f32,
+    pub armor: i32,
+    pub life_steal: f32, // Fraction of damage dealt returned as health
+}
+
+impl Default for EffectiveStats {
+    fn default() -> Self {
+        Self {
+            speed_multiplier: 1.0,
+            damage_multiplier: 1.0,
+            armor: 0,
+            life_steal: 0.0,
+        }
+    }
+}
+
+/// Timed stat modifiers on a unit, from buff abilities and fields
+#[derive(Component, Default)]
+pub struct StatModifiers {
+    pub modifiers: Vec<TimedModifier>,
+    pub effective: EffectiveStats,
+}
+
+impl StatModifiers {
+    pub fn apply(&mut self, modifier: StatModifier, duration: f32, source: &str, stacking: ModifierStacking) {
+        let timer = Timer::from_seconds(duration, TimerMode::Once);
+        let mut same_source = self.modifiers.iter_mut()
+            .filter(|existing| existing.source == source && discriminant(&existing.modifier) == discriminant(&modifier));
+
+        match stacking {
+            ModifierStacking::Refresh => {
+                if let Some(existing) = same_source.next() {
+                    if modifier.strength() >= existing.modifier.strength() {
+                        existing.modifier = modifier;
+                    }
+                    existing.timer = timer;
+                    self.recompute();
+                    return;
+                }
+            }
+            ModifierStacking::Additive => {
+                // At the cap, the copy closest to expiring makes room
+                if same_source.count() >= MAX_ADDITIVE_STACKS {
+                    let oldest = self.modifiers.iter().enumerate()
+                        .filter(|(_, existing)| existing.source == source && discriminant(&existing.modifier) == discriminant(&modifier))
+                        .min_by(|(_, a), (_, b)| a.timer.remaining_secs().total_cmp(&b.timer.remaining_secs()))
+                        .map(|(index, _)| index);
+                    if let Some(index) = oldest {
+                        self.modifiers.remove(index);
+                    }
+                }
+            }
+        }
+
+        self.modifiers.push(TimedModifier {
+            modifier,
+            source: source.to_string(),
+            timer,
+        });
+        self.recompute();
+    }
+
+    fn recompute(&mut self) {
+        let mut stats = EffectiveStats::default();
+        for timed in &self.modifiers {
+            match timed.modifier {
+                StatModifier::SpeedBoost(amount) => stats.speed_multiplier += amount,
+                StatModifier::DamageBoost(amount) => stats.damage_multiplier += amount,
+                StatModifier::ArmorBoost(amount) => stats.armor += amount,
+                StatModifier::LifeSteal(amount) => stats.life_steal += amount,
+            }
+        }
+        stats.speed_multiplier = stats.speed_multiplier.max(0.0);
+        stats.damage_multiplier = stats.damage_multiplier.max(0.0);
+        self.effective = stats;
+    }
+}
+
+impl StatModifier {
+    fn strength(&self) -> f32 {
+        match *self {
+            StatModifier::SpeedBoost(amount) | StatModifier::DamageBoost(amount) | StatModifier::LifeSteal(amount) => amount,
+            StatModifier::ArmorBoost(amount) => amount as f32,
+        }
+    }
+
+    /// Short HUD label
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatModifier::SpeedBoost(_) => "SPD",
+            StatModifier::DamageBoost(_) => "DMG",
+            StatModifier::ArmorBoost(_) => "ARM",
+            StatModifier::LifeSteal(_) => "LS",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            StatModifier::SpeedBoost(_) => Color::srgb(0.3, 0.7, 1.0),
+            StatModifier::DamageBoost(_) => Color::srgb(1.0, 0.4, 0.2),
+            StatModifier::ArmorBoost(_) => Color::srgb(0.7, 0.7, 0.75),
+            StatModifier::LifeSteal(_) => Color::srgb(0.8, 0.1, 0.3),
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct ApplyModifierEvent {
+    pub target: Entity,
+    pub source: String,
+    pub modifier: StatModifier,
+    pub duration: f32,
+    pub stacking: ModifierStacking,
+}
+
+pub fn apply_modifier_events(
+    mut events: EventReader<ApplyModifierEvent>,
+    mut target_q: Query<&mut StatModifiers>,
+) {
+    for event in events.read() {
+        if let Ok(mut modifiers) = target_q.get_mut(event.target) {
+            modifiers.apply(event.modifier.clone(), event.duration, &event.source, event.stacking);
+        }
+    }
+}
+
+pub fn update_stat_modifiers(
+    mut target_q: Query<&mut StatModifiers>,
+    time: Res<Time>,
+) {
+    for mut modifiers in target_q.iter_mut() {
+        if modifiers.modifiers.is_empty() {
+            continue;
+        }
+        for timed in modifiers.modifiers.iter_mut() {
+            timed.timer.tick(time.delta());
+        }
+        let before = modifiers.modifiers.len();
+        modifiers.modifiers.retain(|timed| !timed.timer.finished());
+        if modifiers.modifiers.len() != before {
+            modifiers.recompute();
+        }
+    }
+}
+
+/// Heals attackers with life steal for a share of the damage that actually landed
+pub fn apply_life_steal(
+    mut dealt_events: EventReader<DamageDealtEvent>,
+    mut attacker_q: Query<(&StatModifiers, &mut Health, &Transform)>,
+    mut heal_events: EventWriter<HealEvent>,
+) {
+    for event in dealt_events.read() {
+        let Ok((modifiers, mut health, transform)) = attacker_q.get_mut(event.attacker) else { continue };
+        if modifiers.effective.life_steal <= 0.0 || health.is_dead() {
+            continue;
+        }
+        let amount = (event.amount as f32 * modifiers.effective.life_steal).round() as i32;
+        if amount > 0 && health.current < health.max {
+            health.heal(amount);
+            heal_events.write(HealEvent {
+                target: event.attacker,
+                amount,
+                position: transform.translation,
+            });
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::core::events::{DamageDealtEvent, HealEvent};
+use crate::game::boss::spawn_hazard_zone;
+use crate::game::combat::{Health, CombatStats, DamageImmunity};
+use crate::game::enemy::{SpawnEnemyEvent, EnemyModifiers};
+
+pub struct ElitePlugin;
+
+impl Plugin for ElitePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                update_elite_shields,
+                vampiric_life_steal,
+                sync_elite_outlines,
+                trigger_death_affixes.before(crate::game::combat::cleanup_dead_entities),
+            ));
+    }
+}
+
+/// Extra behavior rolled onto an elite when it spawns
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EliteAffix {
+    Fast,
+    Armored,
+    Vampiric,  // Heals from the damage it deals
+    Explosive, // Leaves a delayed blast where it dies
+    Shielded,  // Periodically becomes immune to damage
+    Splitting, // Breaks into smaller copies on death
+}
+
+impl EliteAffix {
+    pub const ALL: [EliteAffix; 6] = [
+        EliteAffix::Fast,
+        EliteAffix::Armored,
+        EliteAffix::Vampiric,
+        EliteAffix::Explosive,
+        EliteAffix::Shielded,
+        EliteAffix::Splitting,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EliteAffix::Fast => "Fast",
+            EliteAffix::Armored => "Armored",
+            EliteAffix::Vampiric => "Vampiric",
+            EliteAffix::Explosive => "Explosive",
+            EliteAffix::Shielded => "Shielded",
+            EliteAffix::Splitting => "Splitting",
+        }
+    }
+
+    /// Tint and outline color
+    pub fn color(&self) -> Color {
+        match self {
+            EliteAffix::Fast => Color::srgb(0.4, 0.9, 1.0),
+            EliteAffix::Armored => Color::srgb(0.7, 0.7, 0.75),
+            EliteAffix::Vampiric => Color::srgb(0.85, 0.1, 0.2),
+            EliteAffix::Explosive => Color::srgb(1.0, 0.5, 0.1),
+            EliteAffix::Shielded => Color::srgb(0.5, 0.6, 1.0),
+            EliteAffix::Splitting => Color::srgb(0.5, 1.0, 0.4),
+        }
+    }
+}
+
+/// A promoted spawn: one affix for elites, two for champions
+#[derive(Component)]
+pub struct Elite {
+    pub champion: bool,
+    pub affixes: Vec<EliteAffix>,
+    pub enemy_id: String, // What Splitting breaks into
+}
+
+/// Colored copy of an elite's sprite drawn just behind it
+#[derive(Component)]
+pub struct EliteOutline;
+
+/// Death affixes already went off for this elite, in case it outlives its death frame
+#[derive(Component)]
+pub struct DeathAffixesTriggered;
+
+/// Recharge timer for the Shielded affix
+#[derive(Component)]
+pub struct EliteShield {
+    pub timer: Timer,
+}
+
+/// Multipliers and flat bonuses an elite gets over its definition
+pub struct EliteBonus {
+    pub health: f32,
+    pub damage: f32,
+    pub speed: f32,
+    pub armor: i32,
+    pub scale: f32,
+    pub loot_rolls: u32,
+    pub xp: f32,
+}
+
+impl EliteBonus {
+    pub const NONE: EliteBonus = EliteBonus {
+        health: 1.0,
+        damage: 1.0,
+        speed: 1.0,
+        armor: 0,
+        scale: 1.0,
+        loot_rolls: 1,
+        xp: 1.0,
+    };
+}
+
+const FAST_SPEED: f32 = 1.4;
+const ARMORED_ARMOR: i32 = 5;
+const VAMPIRIC_STEAL: f32 = 0.5;      // Fraction of damage dealt healed back
+const SHIELD_INTERVAL: f32 = 6.0;
+const SHIELD_DURATION: f32 = 1.5;
+const EXPLOSION_RADIUS: f32 = 72.0;
+const EXPLOSION_DAMAGE: f32 = 2.0;    // Times the elite's own damage
+const SPLIT_COUNT: u32 = 2;
+const SPLIT_HEALTH: f32 = 0.4;
+const OUTLINE_SCALE: f32 = 1.15;
+
+impl Elite {
+    /// Rolls affixes for a spawn whose modifiers ask for an elite or champion
+    pub fn roll(enemy_id: &str, modifiers: EnemyModifiers, rng: &mut impl Rng) -> Option<Elite> {
+        if !modifiers.elite && !modifiers.champion {
+            return None;
+        }
+        let count = if modifiers.champion { 2 } else { 1 };
+        Some(Elite {
+            champion: modifiers.champion,
+            affixes: EliteAffix::ALL.choose_multiple(rng, count).copied().collect(),
+            enemy_id: enemy_id.to_string(),
+        })
+    }
+
+    pub fn has(&self, affix: EliteAffix) -> bool {
+        self.affixes.contains(&affix)
+    }
+
+    pub fn color(&self) -> Color {
+        self.affixes.first().map_or(Color::srgb(1.0, 0.85, 0.3), EliteAffix::color)
+    }
+
+    /// "Champion Fast Vampiric Orc"
+    pub fn title(&self, name: &str) -> String {
+        let affixes: Vec<&str> = self.affixes.iter().map(EliteAffix::name).collect();
+        let rank = if self.champion { "Champion " } else { "" };
+        format!("{}{} {}", rank, affixes.join(" "), name)
+    }
+
+    pub fn bonus(&self) -> EliteBonus {
+        let (health, damage, scale, loot_rolls, xp) = if self.champion {
+            (4.0, 2.0, 1.5, 3, 5.0)
+        } else {
+            (2.5, 1.5, 1.3, 2, 3.0)
+        };
+        EliteBonus {
+            health,
+            damage,
+            speed: if self.has(EliteAffix::Fast) { FAST_SPEED } else { 1.0 },
+            armor: if self.has(EliteAffix::Armored) { ARMORED_ARMOR } else { 0 },
+            scale,
+            loot_rolls,
+            xp,
+        }
+    }
+}
+
+/// Outline child for a freshly spawned elite, sharing its sprite sheet
+pub fn outline_sprite(elite: &Elite, sprite: &Sprite) -> impl Bundle {
+    (
+        EliteOutline,
+        Sprite {
+            color: elite.color().with_alpha(0.8),
+            custom_size: sprite.custom_size.map(|size| size * OUTLINE_SCALE),
+            ..sprite.clone()
+        },
+        Transform::from_xyz(0.0, 0.0, -0.1),
+    )
+}
+
+pub fn shield() -> EliteShield {
+    EliteShield {
+        timer: Timer::from_seconds(SHIELD_INTERVAL, TimerMode::Repeating),
+    }
+}
+
+fn update_elite_shields(
+    mut commands: Commands,
+    mut shield_q: Query<(Entity, &mut EliteShield, Has<DamageImmunity>)>,
+    time: Res<Time>,
+) {
+    for (entity, mut shield, immune) in shield_q.iter_mut() {
+        // Recharge only counts down while the shield is down
+        if immune {
+            continue;
+        }
+        shield.timer.tick(time.delta());
+        if shield.timer.just_finished() {
+            commands.entity(entity).insert(DamageImmunity {
+                timer: Timer::from_seconds(SHIELD_DURATION, TimerMode::Once),
+            });
+        }
+    }
+}
+
+fn vampiric_life_steal(
+    mut dealt_events: EventReader<DamageDealtEvent>,
+    mut elite_q: Query<(&Elite, &mut Health, &Transform)>,
+    mut heal_events: EventWriter<HealEvent>,
+) {
+    for event in dealt_events.read() {
+        let Ok((elite, mut health, transform)) = elite_q.get_mut(event.attacker) else { continue };
+        if elite.has(EliteAffix::Vampiric) {
+            let amount = ((event.amount as f32 * VAMPIRIC_STEAL) as i32).max(1);
+            health.heal(amount);
+            heal_events.write(HealEvent {
+                target: event.attacker,
+                amount,
+                position: transform.translation,
+            });
+        }
+    }
+}
+
+/// Keeps each outline on the same animation frame and facing as its elite
+fn sync_elite_outlines(
+    elite_q: Query<&Sprite, (With<Elite>, Without<EliteOutline>)>,
+    mut outline_q: Query<(&ChildOf, &mut Sprite), With<EliteOutline>>,
+) {
+    for (child_of, mut outline) in outline_q.iter_mut() {
+        let Ok(sprite) = elite_q.get(child_of.parent()) else { continue };
+        if let (Some(outline_atlas), Some(atlas)) = (outline.texture_atlas.as_mut(), sprite.texture_atlas.as_ref()) {
+            outline_atlas.index = atlas.index;
+        }
+        outline.flip_x = sprite.flip_x;
+    }
+}
+
+/// Explosive and Splitting fire here, once, before the dead elite is despawned
+fn trigger_death_affixes(
+    mut commands: Commands,
+    elite_q: Query<(Entity, &Elite, &Health, &CombatStats, &Transform), Without<DeathAffixesTriggered>>,
+    mut spawn_events: EventWriter<SpawnEnemyEvent>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for (entity, elite, health, stats, transform) in elite_q.iter() {
+        if !health.is_dead() {
+            continue;
+        }
+        commands.entity(entity).insert(DeathAffixesTriggered);
+
+        if elite.has(EliteAffix::Explosive) {
+            let damage = (stats.damage as f32 * EXPLOSION_DAMAGE) as i32;
+            spawn_hazard_zone(&mut commands, entity, transform.translation.truncate(), EXPLOSION_RADIUS, damage, 0.6);
+        }
+
+        if elite.has(EliteAffix::Splitting) {
+            for _ in 0..SPLIT_COUNT {
+                let offset = Vec2::new(rng.gen_range(-24.0..=24.0), rng.gen_range(-24.0..=24.0));
+                spawn_events.write(SpawnEnemyEvent {
+                    position: transform.translation + offset.extend(0.0),
+                    enemy_id: elite.enemy_id.clone(),
+                    modifiers: EnemyModifiers {
+                        health: SPLIT_HEALTH,
+                        ..default()
+                    },
+                });
+            }
+        }
+    }
+}
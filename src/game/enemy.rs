@@ -1,8 +1,20 @@
 use bevy::prelude::*;
-use crate::game::animation::{AnimationController, AnimationClip};
+use crate::game::animation::AnimationController;
 use crate::game::combat::{Health, CombatStats};
+use crate::game::combat::effects::StatusEffects;
+use crate::game::combat::modifiers::StatModifiers;
 use crate::game::movement::{Velocity, Collider};
-use crate::game::player::FacingDirection;
+use crate::game::player::{Player, FacingDirection};
+use crate::game::enemy_archetypes::{self, EnemyAttackEvent, Charging};
+use crate::game::boss;
+use crate::game::elite::{self, Elite, EliteAffix, EliteBonus};
+use crate::game::enemy_definitions::{EnemyDefinition, EnemyDefinitions, EnemyRoster};
+use rand::Rng;
+use std::collections::HashMap;
+use serde::Deserialize;
+use crate::world::tile_grid::TileGrid;
+use crate::world::pathfinding::{FlowField, find_path};
+use crate::world::spawn_placement::SpawnPlacement;
 
 pub struct EnemyPlugin;
 
@@ -12,10 +24,9 @@ impl Plugin for EnemyPlugin {
             .add_event::<SpawnEnemyEvent>()
             .add_event::<SpawnBossEvent>()
             .init_resource::<EnemyAssets>()
-            .add_systems(Startup, load_enemy_assets)
             .add_systems(Update, (
                 handle_spawn_events,
-                (enemy_ai_system, update_enemy_behavior),
+                enemy_ai_system,
                 update_enemy_sprite_direction.after(crate::game::animation::update_animations),
             ));
     }
@@ -30,9 +41,29 @@ pub struct Enemy {
     pub patrol_origin: Vec2,
     pub behavior_timer: Timer,
     pub move_speed: f32,
+    pub has_fled: bool, // Each enemy only breaks off once, then fights to the end
 }
 
-#[derive(Clone, Copy, PartialEq)]
+/// Per-enemy A* route, used when the enemy is outside the shared flow field
+#[derive(Component)]
+pub struct PathFollower {
+    pub path: Vec<IVec2>,     // Remaining cells, next one first
+    pub goal: Option<IVec2>,
+    pub repath_timer: Timer,
+}
+
+impl Default for PathFollower {
+    fn default() -> Self {
+        Self {
+            path: Vec::new(),
+            goal: None,
+            repath_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum EnemyType {
     // Basic enemies
     Goblin,
@@ -47,6 +78,26 @@ pub enum EnemyType {
     DragonKnight,
 }
 
+const PATROL_RADIUS: f32 = 96.0;
+const ATTACK_WINDUP: f32 = 0.6;
+const FLEE_HEALTH: f32 = 0.25; // Regular enemies run below this fraction of their health
+const FLEE_TIME: f32 = 3.0;    // Longest an enemy runs before turning to fight again
+
+impl Enemy {
+    /// Switches state and restarts `behavior_timer` as that state's countdown
+    pub fn set_state(&mut self, state: AIState, duration: f32) {
+        self.ai_state = state;
+        self.behavior_timer = Timer::from_seconds(duration, TimerMode::Once);
+    }
+
+    /// Interrupts whatever the enemy is doing for `duration` seconds
+    pub fn stun(&mut self, duration: f32) {
+        if duration > 0.0 {
+            self.set_state(AIState::Stunned, duration);
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 pub enum AIState {
     Idle,
@@ -57,59 +108,137 @@ pub enum AIState {
     Stunned,
 }
 
+/// Phase-driven boss state, advanced by the systems in `game::boss`
 #[derive(Component)]
 pub struct Boss {
-    pub phase: u8,
+    pub phase: u8,               // 1-based index into the boss's phase table
     pub enrage_timer: Timer,
+    pub enraged: bool,
+    pub pattern_timer: Timer,
+    pub pattern_index: usize,    // Next pattern to fire within the current phase
+}
+
+/// Loot and experience granted when the enemy dies, copied from its definition
+#[derive(Component)]
+pub struct EnemyRewards {
+    pub loot_table: Option<String>,
+    pub xp: u32,
+    pub loot_rolls: u32, // Times the loot table is rolled; elites get extra
+}
+
+/// Multipliers applied on top of an enemy's definition when it spawns
+#[derive(Clone, Copy, Deserialize)]
+pub struct EnemyModifiers {
+    #[serde(default = "one")]
+    pub health: f32,
+    #[serde(default = "one")]
+    pub damage: f32,
+    #[serde(default = "one")]
+    pub speed: f32,
+    #[serde(default)]
+    pub elite: bool,
+    #[serde(default)]
+    pub champion: bool, // A stronger elite with two affixes
+}
+
+fn one() -> f32 { 1.0 }
+
+impl Default for EnemyModifiers {
+    fn default() -> Self {
+        Self {
+            health: 1.0,
+            damage: 1.0,
+            speed: 1.0,
+            elite: false,
+            champion: false,
+        }
+    }
 }
 
+/// Spawns the enemy definition `enemy_id` from the roster
 #[derive(Event)]
 pub struct SpawnEnemyEvent {
     pub position: Vec3,
-    pub enemy_type: EnemyType,
+    pub enemy_id: String,
+    pub modifiers: EnemyModifiers,
 }
 
 #[derive(Event)]
 pub struct SpawnBossEvent {
     pub position: Vec3,
-    pub boss_type: EnemyType,
+    pub boss_id: String,
+    pub modifiers: EnemyModifiers,
+}
+
+impl EnemyType {
+    pub fn is_boss(&self) -> bool {
+        matches!(self, EnemyType::GoblinKing | EnemyType::LichLord | EnemyType::DragonKnight)
+    }
+
+    /// Key used by loot tables, achievements and the built-in enemy definitions
+    pub fn id(&self) -> &'static str {
+        match self {
+            EnemyType::Goblin => "goblin",
+            EnemyType::Skeleton => "skeleton",
+            EnemyType::Orc => "orc",
+            EnemyType::DarkKnight => "dark_knight",
+            EnemyType::Necromancer => "necromancer",
+            EnemyType::GoblinKing => "goblin_king",
+            EnemyType::LichLord => "lich_lord",
+            EnemyType::DragonKnight => "dragon_knight",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            EnemyType::Goblin => "Goblin",
+            EnemyType::Skeleton => "Skeleton",
+            EnemyType::Orc => "Orc",
+            EnemyType::DarkKnight => "Dark Knight",
+            EnemyType::Necromancer => "Necromancer",
+            EnemyType::GoblinKing => "Goblin King",
+            EnemyType::LichLord => "Lich Lord",
+            EnemyType::DragonKnight => "Dragon Knight",
+        }
+    }
 }
 
+/// Sprite atlas per enemy definition id, rebuilt whenever the roster (re)loads
 #[derive(Resource, Default)]
 pub struct EnemyAssets {
-    pub textures: Vec<Handle<Image>>,
-    pub layouts: Vec<Handle<TextureAtlasLayout>>,
+    pub atlases: HashMap<String, EnemyAtlas>,
 }
 
-fn load_enemy_assets(
-    mut enemy_assets: ResMut<EnemyAssets>,
-    asset_server: Res<AssetServer>,
-    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
-) {
-    // Load textures - using fallback texture for now
-    enemy_assets.textures.push(asset_server.load("sprites/test_p_sprite.png"));
-    
-    // Create layouts
-    let layout = TextureAtlasLayout::from_grid(
-        UVec2::new(32, 32),
-        4, 2,
-        None, None,
-    );
-    enemy_assets.layouts.push(layouts.add(layout));
+#[derive(Clone)]
+pub struct EnemyAtlas {
+    pub texture: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
 }
 
 fn handle_spawn_events(
     mut commands: Commands,
     mut enemy_events: EventReader<SpawnEnemyEvent>,
     mut boss_events: EventReader<SpawnBossEvent>,
+    definitions: Option<Res<EnemyDefinitions>>,
+    rosters: Res<Assets<EnemyRoster>>,
     enemy_assets: Res<EnemyAssets>,
+    placement: SpawnPlacement,
 ) {
-    for event in enemy_events.read() {
-        spawn_enemy(&mut commands, &enemy_assets, event.position, event.enemy_type);
-    }
-    
-    for event in boss_events.read() {
-        spawn_boss(&mut commands, &enemy_assets, event.position, event.boss_type);
+    let Some(roster) = definitions.and_then(|definitions| rosters.get(&definitions.handle)) else { return };
+
+    let requests = enemy_events.read().map(|event| (event.position, &event.enemy_id, event.modifiers))
+        .chain(boss_events.read().map(|event| (event.position, &event.boss_id, event.modifiers)));
+    for (mut position, id, modifiers) in requests {
+        // Summons and scripted spawns can land inside walls; move them to the closest open floor
+        if !placement.is_spawnable(position.truncate()) {
+            if let Some(open) = placement.nearest_spawnable(position.truncate(), |_| true) {
+                position = open.extend(position.z);
+            }
+        }
+        match roster.get(id) {
+            Some(definition) => spawn_enemy(&mut commands, &enemy_assets, position, definition, modifiers),
+            None => println!("Unknown enemy definition '{}'", id),
+        }
     }
 }
 
@@ -117,180 +246,311 @@ fn spawn_enemy(
     commands: &mut Commands,
     assets: &EnemyAssets,
     position: Vec3,
-    enemy_type: EnemyType,
+    definition: &EnemyDefinition,
+    modifiers: EnemyModifiers,
 ) {
-    let (health, damage, armor, speed, color) = match enemy_type {
-        EnemyType::Goblin => (30, 5, 0, 80.0, Color::linear_rgb(0.0, 0.8, 0.0)),
-        EnemyType::Skeleton => (50, 8, 2, 60.0, Color::linear_rgb(0.9, 0.9, 0.9)),
-        EnemyType::Orc => (80, 12, 5, 40.0, Color::linear_rgb(0.6, 0.4, 0.0)),
-        EnemyType::DarkKnight => (150, 20, 10, 50.0, Color::linear_rgb(0.2, 0.0, 0.2)),
-        EnemyType::Necromancer => (100, 15, 3, 30.0, Color::linear_rgb(0.5, 0.0, 0.5)),
-        _ => (100, 10, 5, 50.0, Color::linear_rgb(1.0, 1.0, 1.0)),
-    };
-
-    let mut anim = AnimationController::new();
-    anim.add_animation("idle", AnimationClip::new(0, 3, 0.3, true));
-    anim.add_animation("walk", AnimationClip::new(4, 7, 0.15, true));
-    anim.play("idle");
-
-    if let (Some(texture), Some(layout)) = (assets.textures.first(), assets.layouts.first()) {
-        commands.spawn((
-            Enemy {
-                enemy_type,
-                ai_state: AIState::Idle,
-                detection_range: 300.0,
-                attack_range: 40.0,
-                patrol_origin: position.truncate(),
-                behavior_timer: Timer::from_seconds(2.0, TimerMode::Repeating),
-                move_speed: speed,
-            },
-            Health::new(health),
-            CombatStats {
-                damage,
-                armor,
-                crit_chance: 0.1,
-                crit_multiplier: 1.5,
-            },
-            Velocity(Vec2::ZERO),
-            Collider { size: Vec2::splat(28.0) },
-            FacingDirection::default(),
-            Sprite {
-                image: texture.clone(),
-                texture_atlas: Some(TextureAtlas {
-                    layout: layout.clone(),
-                    index: 0,
-                }),
-                color,
-                custom_size: Some(Vec2::splat(32.0)),
-                ..default()
-            },
-            Transform::from_translation(position),
-            anim,
-        ));
-    }
-}
+    let Some(atlas) = assets.atlases.get(&definition.id) else { return };
+    let enemy_type = definition.archetype;
 
-fn spawn_boss(
-    commands: &mut Commands,
-    assets: &EnemyAssets,
-    position: Vec3,
-    boss_type: EnemyType,
-) {
-    let (health, damage, armor, speed, color) = match boss_type {
-        EnemyType::GoblinKing => (500, 25, 5, 30.0, Color::linear_rgb(0.0, 1.0, 0.0)),
-        EnemyType::LichLord => (800, 40, 10, 20.0, Color::linear_rgb(0.5, 0.0, 1.0)),
-        EnemyType::DragonKnight => (1200, 60, 15, 25.0, Color::linear_rgb(1.0, 0.0, 0.0)),
-        _ => (500, 25, 5, 30.0, Color::linear_rgb(1.0, 1.0, 1.0)),
+    // Bosses are never promoted
+    let elite = Elite::roll(&definition.id, modifiers, &mut rand::thread_rng()).filter(|_| !enemy_type.is_boss());
+    let bonus = elite.as_ref().map_or(EliteBonus::NONE, Elite::bonus);
+    let color = match &elite {
+        Some(elite) if definition.sprite.tint.is_none() => elite.color(),
+        _ => definition.tint(),
     };
+    let name = match &elite {
+        Some(elite) => elite.title(&definition.name),
+        None => definition.name.clone(),
+    };
+    let sprite = Sprite {
+        image: atlas.texture.clone(),
+        texture_atlas: Some(TextureAtlas {
+            layout: atlas.layout.clone(),
+            index: 0,
+        }),
+        color,
+        custom_size: Some(Vec2::splat(definition.sprite.size * bonus.scale)),
+        ..default()
+    };
+
+    let mut entity = commands.spawn((
+        Enemy {
+            enemy_type,
+            ai_state: AIState::Idle,
+            detection_range: definition.detection_range,
+            attack_range: enemy_archetypes::attack_range(enemy_type),
+            patrol_origin: position.truncate(),
+            behavior_timer: Timer::from_seconds(if enemy_type.is_boss() { 1.0 } else { 2.0 }, TimerMode::Repeating),
+            move_speed: definition.speed * modifiers.speed * bonus.speed,
+            has_fled: false,
+        },
+        Name::new(name),
+        EnemyRewards {
+            loot_table: definition.loot_table.clone(),
+            xp: (definition.xp as f32 * bonus.xp) as u32,
+            loot_rolls: bonus.loot_rolls,
+        },
+        Health::new((definition.health as f32 * modifiers.health * bonus.health) as i32),
+        CombatStats {
+            damage: (definition.damage as f32 * modifiers.damage * bonus.damage) as i32,
+            armor: definition.armor + bonus.armor,
+            crit_chance: definition.crit_chance,
+            crit_multiplier: definition.crit_multiplier,
+        },
+        definition.resistances,
+        StatusEffects::default(),
+        StatModifiers::default(),
+        Velocity(Vec2::ZERO),
+        Collider { size: Vec2::from(definition.collider) * bonus.scale },
+        PathFollower::default(),
+        FacingDirection::default(),
+        sprite.clone(),
+        Transform::from_translation(position),
+        definition.animation_controller(),
+    ));
 
-    let mut anim = AnimationController::new();
-    anim.add_animation("idle", AnimationClip::new(0, 3, 0.3, true));
-    anim.add_animation("walk", AnimationClip::new(4, 7, 0.15, true));
-    anim.play("idle");
-
-    if let (Some(texture), Some(layout)) = (assets.textures.first(), assets.layouts.first()) {
-        commands.spawn((
-            Enemy {
-                enemy_type: boss_type,
-                ai_state: AIState::Idle,
-                detection_range: 500.0,
-                attack_range: 60.0,
-                patrol_origin: position.truncate(),
-                behavior_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
-                move_speed: speed,
-            },
-            Boss {
-                phase: 1,
-                enrage_timer: Timer::from_seconds(10.0, TimerMode::Once),
-            },
-            Health::new(health),
-            CombatStats {
-                damage,
-                armor,
-                crit_chance: 0.2,
-                crit_multiplier: 2.0,
-            },
-            Velocity(Vec2::ZERO),
-            Collider { size: Vec2::splat(48.0) },
-            FacingDirection::default(),
-            Sprite {
-                image: texture.clone(),
-                texture_atlas: Some(TextureAtlas {
-                    layout: layout.clone(),
-                    index: 0,
-                }),
-                color,
-                custom_size: Some(Vec2::splat(64.0)),
-                ..default()
-            },
-            Transform::from_translation(position),
-            anim,
-        ));
+    if let Some(elite) = elite {
+        entity.with_child(elite::outline_sprite(&elite, &sprite));
+        if elite.has(EliteAffix::Shielded) {
+            entity.insert(elite::shield());
+        }
+        entity.insert(elite);
+    }
+    if enemy_type.is_boss() {
+        entity.insert(Boss {
+            phase: 1,
+            enrage_timer: Timer::from_seconds(boss::enrage_time(enemy_type), TimerMode::Once),
+            enraged: false,
+            pattern_timer: Timer::from_seconds(boss::phase_table(enemy_type)[0].pattern_interval, TimerMode::Once),
+            pattern_index: 0,
+        });
     }
 }
 
 fn enemy_ai_system(
-    mut enemy_q: Query<(&mut Enemy, &Transform, &mut Velocity, &mut AnimationController, &mut FacingDirection)>,
-    player_q: Query<&Transform, (With<crate::game::player::Player>, Without<Enemy>)>,
+    mut enemy_q: Query<(
+        Entity,
+        &mut Enemy,
+        &mut Transform,
+        &mut Velocity,
+        &mut AnimationController,
+        &mut FacingDirection,
+        &mut PathFollower,
+        &Health,
+        &StatusEffects,
+        &StatModifiers,
+        Has<Boss>,
+    ), (Without<Player>, Without<Charging>)>,
+    player_q: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    grid: Res<TileGrid>,
+    flow_field: Res<FlowField>,
+    mut attack_events: EventWriter<EnemyAttackEvent>,
     time: Res<Time>,
 ) {
     let Ok(player_tf) = player_q.single() else { return };
+    let player_pos = player_tf.translation.truncate();
+    let mut rng = rand::thread_rng();
     
-    for (mut enemy, enemy_tf, mut velocity, mut anim, mut facing) in enemy_q.iter_mut() {
-        enemy.behavior_timer.tick(time.delta());
+    for (entity, mut enemy, mut enemy_tf, mut velocity, mut anim, mut facing, mut follower, health, statuses, modifiers, is_boss) in enemy_q.iter_mut() {
+        // Slows stretch out wind-ups and attack recovery; stuns and idle waits run in real time
+        let timer_rate = match enemy.ai_state {
+            AIState::Attacking | AIState::Chasing => statuses.attack_speed_multiplier(),
+            _ => 1.0,
+        };
+        enemy.behavior_timer.tick(time.delta().mul_f32(timer_rate));
         
-        let to_player = player_tf.translation - enemy_tf.translation;
+        let position = enemy_tf.translation.truncate();
+        let to_player = player_pos - position;
         let distance = to_player.length();
         
+        // Badly hurt enemies break off and run once; bosses fight to the end
+        if !is_boss
+            && !enemy.has_fled
+            && health.percentage() < FLEE_HEALTH
+            && distance < enemy.detection_range
+            && !matches!(enemy.ai_state, AIState::Fleeing | AIState::Stunned)
+        {
+            enemy.has_fled = true;
+            enemy.set_state(AIState::Fleeing, FLEE_TIME);
+        }
+        
+        // Only a wind-up swells the sprite
+        if enemy.ai_state != AIState::Attacking {
+            enemy_tf.scale = Vec3::ONE;
+        }
+        
         // State machine
+        let mut direction = Vec2::ZERO;
+        let mut speed = enemy.move_speed * statuses.speed_multiplier() * modifiers.effective.speed_multiplier;
         match enemy.ai_state {
             AIState::Idle => {
-                velocity.0 = Vec2::ZERO;
                 if distance < enemy.detection_range {
-                    enemy.ai_state = AIState::Chasing;
+                    follower.path.clear();
+                    enemy.set_state(AIState::Chasing, 0.0);
+                } else if enemy.behavior_timer.finished() {
+                    // Wander to a random walkable spot around the patrol origin
+                    let target = enemy.patrol_origin + Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU)) * rng.gen_range(0.0..PATROL_RADIUS);
+                    let start = grid.world_to_cell(position);
+                    follower.path = find_path(&grid, start, grid.world_to_cell(target), 200).unwrap_or_default();
+                    follower.goal = None;
+                    if follower.path.is_empty() {
+                        enemy.set_state(AIState::Idle, 1.0);
+                    } else {
+                        enemy.set_state(AIState::Patrolling, 0.0);
+                    }
                 }
                 if anim.current != "idle" {
                     anim.play("idle");
                 }
             }
+            AIState::Patrolling => {
+                if distance < enemy.detection_range {
+                    follower.path.clear();
+                    enemy.set_state(AIState::Chasing, 0.0);
+                } else if let Some(next) = follow_path(&mut follower, &grid, position) {
+                    direction = (next - position).normalize_or_zero();
+                    speed *= 0.5;
+                    if anim.current != "walk" {
+                        anim.play("walk");
+                    }
+                } else {
+                    // Reached the spot, linger a little before the next one
+                    enemy.set_state(AIState::Idle, rng.gen_range(1.0..2.5));
+                }
+            }
             AIState::Chasing => {
                 if distance > enemy.detection_range * 1.5 {
-                    enemy.ai_state = AIState::Idle;
+                    enemy.set_state(AIState::Idle, 1.0);
+                } else if distance <= enemy.attack_range && enemy.behavior_timer.finished() {
+                    enemy.set_state(AIState::Attacking, ATTACK_WINDUP);
+                    anim.play("attack");
+                } else if enemy_archetypes::keep_distance(enemy.enemy_type).is_some_and(|keep| distance < keep) {
+                    // Ranged enemies back off to keep the player at arm's length
+                    direction = -to_player.normalize_or_zero();
+                    if anim.current != "walk" {
+                        anim.play("walk");
+                    }
+                } else if distance <= enemy.attack_range {
+                    // In range but still recovering from the last attack
+                    if anim.current != "idle" {
+                        anim.play("idle");
+                    }
                 } else {
-                    // Move towards player
-                    let direction = to_player.truncate().normalize_or_zero();
-                    velocity.0 = direction * enemy.move_speed;
+                    // Move towards player along the walkable tiles
+                    let target = chase_target(
+                        position,
+                        player_pos,
+                        &grid,
+                        &flow_field,
+                        &mut follower,
+                        &time,
+                    );
+                    direction = (target - position).normalize_or_zero();
                     
-                    // Update facing direction based on movement
-                    if direction.x < 0.0 {
-                        *facing = FacingDirection::Left;
-                    } else if direction.x > 0.0 {
-                        *facing = FacingDirection::Right;
+                    if anim.current != "walk" {
+                        anim.play("walk");
                     }
-                    
+                }
+            }
+            AIState::Attacking => {
+                // Telegraph: the enemy swells up during the wind-up, then strikes
+                enemy_tf.scale = Vec3::splat(1.0 + 0.25 * enemy.behavior_timer.fraction());
+                if to_player.x != 0.0 {
+                    *facing = if to_player.x < 0.0 { FacingDirection::Left } else { FacingDirection::Right };
+                }
+                
+                if enemy.behavior_timer.finished() {
+                    enemy_tf.scale = Vec3::ONE;
+                    attack_events.write(EnemyAttackEvent {
+                        attacker: entity,
+                        enemy_type: enemy.enemy_type,
+                        origin: position,
+                        target: player_pos,
+                    });
+                    let cooldown = enemy_archetypes::attack_cooldown(enemy.enemy_type);
+                    enemy.set_state(AIState::Chasing, cooldown);
+                }
+            }
+            AIState::Fleeing => {
+                let away = -to_player.normalize_or_zero();
+                if distance > enemy.detection_range {
+                    enemy.patrol_origin = position;
+                    enemy.set_state(AIState::Idle, 1.0);
+                } else if enemy.behavior_timer.finished() || !grid.is_walkable(position + away * grid.tile_size * 0.5) {
+                    // Out of time or backed into a wall: turn and fight
+                    follower.path.clear();
+                    enemy.set_state(AIState::Chasing, 0.0);
+                } else {
+                    direction = away;
                     if anim.current != "walk" {
                         anim.play("walk");
                     }
                 }
             }
-            _ => {
-                velocity.0 = Vec2::ZERO;
+            AIState::Stunned => {
+                if anim.current != "hurt" {
+                    anim.play("hurt");
+                }
+                if enemy.behavior_timer.finished() {
+                    follower.path.clear();
+                    enemy.set_state(AIState::Chasing, 0.0);
+                }
             }
         }
+        
+        velocity.0 = direction * speed;
+        
+        // Update facing direction based on movement
+        if direction.x < 0.0 {
+            *facing = FacingDirection::Left;
+        } else if direction.x > 0.0 {
+            *facing = FacingDirection::Right;
+        }
     }
 }
 
-fn update_enemy_behavior(
-    mut boss_q: Query<(&mut Boss, &mut Enemy, &Health)>,
-    _time: Res<Time>,
-) {
-    for (mut boss, mut enemy, health) in boss_q.iter_mut() {
-        // Boss gets more aggressive at low health - enter phase 2
-        if health.percentage() < 0.3 && boss.phase == 1 {
-            boss.phase = 2;
-            enemy.move_speed *= 1.5;
+// Next point to steer at while chasing: the shared flow field when the enemy is inside it,
+// otherwise its own A* path, falling back to a straight line when no route is known
+fn chase_target(
+    position: Vec2,
+    player_pos: Vec2,
+    grid: &TileGrid,
+    flow_field: &FlowField,
+    follower: &mut PathFollower,
+    time: &Time,
+) -> Vec2 {
+    let cell = grid.world_to_cell(position);
+    let player_cell = grid.world_to_cell(player_pos);
+    if (player_cell - cell).abs().max_element() <= 1 {
+        return player_pos;
+    }
+
+    if let Some(next) = flow_field.next_cell(grid, cell) {
+        follower.path.clear();
+        return grid.cell_to_world(next);
+    }
+
+    follower.repath_timer.tick(time.delta());
+    if follower.goal != Some(player_cell) || follower.repath_timer.just_finished() {
+        follower.path = find_path(grid, cell, player_cell, 2000).unwrap_or_default();
+        follower.goal = Some(player_cell);
+    }
+
+    follow_path(follower, grid, position).unwrap_or(player_pos)
+}
+
+// Drops the path cells already reached and returns the center of the next one
+fn follow_path(follower: &mut PathFollower, grid: &TileGrid, position: Vec2) -> Option<Vec2> {
+    let cell = grid.world_to_cell(position);
+    while let Some(&next) = follower.path.first() {
+        if next == cell || grid.cell_to_world(next).distance(position) < grid.tile_size * 0.25 {
+            follower.path.remove(0);
+        } else {
+            break;
         }
     }
+    follower.path.first().map(|&next| grid.cell_to_world(next))
 }
 
 /// System to update enemy sprite based on facing direction
@@ -0,0 +1,363 @@
+use bevy::prelude::*;
+use crate::core::events::{CombatEvent, HitKind, DamageType};
+use crate::game::combat::CombatStats;
+use crate::game::enemy::{Enemy, EnemyType, AIState, SpawnEnemyEvent, EnemyModifiers};
+use crate::game::movement::Velocity;
+use crate::game::player::{Player, FacingDirection};
+use crate::world::tile_grid::TileGrid;
+
+pub struct EnemyArchetypePlugin;
+
+impl Plugin for EnemyArchetypePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<EnemyAttackEvent>()
+            .add_systems(Update, (
+                resolve_enemy_attacks,
+                update_charges,
+                update_enemy_projectiles,
+                update_corpses,
+                update_slam_effects,
+            ));
+    }
+}
+
+/// Sent by the AI when an enemy's wind-up finishes; each archetype resolves it its own way
+#[derive(Event)]
+pub struct EnemyAttackEvent {
+    pub attacker: Entity,
+    pub enemy_type: EnemyType,
+    pub origin: Vec2,
+    pub target: Vec2,
+}
+
+#[derive(Component)]
+pub struct EnemyProjectile {
+    pub damage: i32,
+    pub damage_type: DamageType,
+    pub owner: Entity,
+    pub radius: f32,
+    pub lifetime: Timer,
+}
+
+#[derive(Clone, Copy)]
+pub enum EnemyProjectileKind {
+    Arrow,
+    DarkBolt,
+    Fireball,
+}
+
+/// A Dark Knight mid-charge; the AI leaves it alone until the charge ends
+#[derive(Component)]
+pub struct Charging {
+    pub direction: Vec2,
+    pub timer: Timer,
+    pub has_hit: bool,
+}
+
+/// Left behind by fallen enemies for Necromancers to raise
+#[derive(Component)]
+pub struct Corpse {
+    pub lifetime: Timer,
+}
+
+#[derive(Component)]
+struct SlamEffect {
+    lifetime: Timer,
+}
+
+const ARROW_SPEED: f32 = 320.0;
+const DARK_BOLT_SPEED: f32 = 220.0;
+const FIREBALL_SPEED: f32 = 180.0;
+const CHARGE_SPEED: f32 = 450.0;
+const CHARGE_DURATION: f32 = 0.45;
+const SLAM_RADIUS: f32 = 70.0;
+const RAISE_RANGE: f32 = 200.0;
+const MAX_SUMMONED_GOBLINS: usize = 3;
+
+/// How close the enemy needs to be before it starts an attack wind-up
+pub fn attack_range(enemy_type: EnemyType) -> f32 {
+    match enemy_type {
+        EnemyType::Goblin => 40.0,
+        EnemyType::Skeleton => 260.0,
+        EnemyType::Orc => 55.0,
+        EnemyType::DarkKnight => 200.0,
+        EnemyType::Necromancer => 240.0,
+        EnemyType::GoblinKing | EnemyType::LichLord | EnemyType::DragonKnight => 60.0,
+    }
+}
+
+/// Ranged archetypes back away when the player gets closer than this
+pub fn keep_distance(enemy_type: EnemyType) -> Option<f32> {
+    match enemy_type {
+        EnemyType::Skeleton => Some(150.0),
+        EnemyType::Necromancer => Some(170.0),
+        _ => None,
+    }
+}
+
+/// Seconds between the end of one attack and the next wind-up
+pub fn attack_cooldown(enemy_type: EnemyType) -> f32 {
+    match enemy_type {
+        EnemyType::Goblin => 0.4,
+        EnemyType::Skeleton => 1.2,
+        EnemyType::Orc => 1.0,
+        EnemyType::DarkKnight => 2.0,
+        EnemyType::Necromancer => 2.5,
+        EnemyType::GoblinKing | EnemyType::LichLord | EnemyType::DragonKnight => 0.8,
+    }
+}
+
+pub fn spawn_corpse(commands: &mut Commands, position: Vec3) {
+    commands.spawn((
+        Corpse {
+            lifetime: Timer::from_seconds(12.0, TimerMode::Once),
+        },
+        Sprite {
+            color: Color::srgba(0.35, 0.3, 0.3, 0.7),
+            custom_size: Some(Vec2::new(24.0, 10.0)),
+            ..default()
+        },
+        Transform::from_translation(position.truncate().extend(0.5)),
+    ));
+}
+
+pub fn spawn_enemy_projectile(
+    commands: &mut Commands,
+    kind: EnemyProjectileKind,
+    origin: Vec2,
+    direction: Vec2,
+    damage: i32,
+    owner: Entity,
+) {
+    let (speed, damage_type, color, size) = match kind {
+        EnemyProjectileKind::Arrow => (ARROW_SPEED, DamageType::Physical, Color::srgb(0.8, 0.7, 0.5), Vec2::new(16.0, 3.0)),
+        EnemyProjectileKind::DarkBolt => (DARK_BOLT_SPEED, DamageType::Magic, Color::srgb(0.5, 1.0, 0.5), Vec2::splat(10.0)),
+        EnemyProjectileKind::Fireball => (FIREBALL_SPEED, DamageType::Fire, Color::srgb(1.0, 0.45, 0.1), Vec2::splat(14.0)),
+    };
+
+    commands.spawn((
+        EnemyProjectile {
+            damage,
+            damage_type,
+            owner,
+            radius: size.y.max(6.0),
+            lifetime: Timer::from_seconds(3.0, TimerMode::Once),
+        },
+        Velocity(direction * speed),
+        Sprite {
+            color,
+            custom_size: Some(size),
+            ..default()
+        },
+        Transform::from_translation(origin.extend(5.0))
+            .with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
+    ));
+}
+
+fn resolve_enemy_attacks(
+    mut commands: Commands,
+    mut attack_events: EventReader<EnemyAttackEvent>,
+    mut combat_events: EventWriter<CombatEvent>,
+    mut spawn_events: EventWriter<SpawnEnemyEvent>,
+    attacker_q: Query<&CombatStats, With<Enemy>>,
+    enemy_q: Query<(&Transform, &Enemy)>,
+    corpse_q: Query<(Entity, &Transform), With<Corpse>>,
+    player_q: Query<(Entity, &Transform), With<Player>>,
+) {
+    let Ok((player_entity, player_tf)) = player_q.single() else { return };
+    let player_pos = player_tf.translation.truncate();
+
+    for event in attack_events.read() {
+        let Ok(stats) = attacker_q.get(event.attacker) else { continue };
+        let to_target = event.target - event.origin;
+        let direction = to_target.normalize_or_zero();
+        let hit_player = |damage_type| CombatEvent {
+            attacker: event.attacker,
+            target: player_entity,
+            damage: stats.damage,
+            damage_type,
+            position: player_tf.translation,
+            kind: HitKind::Direct,
+        };
+
+        match event.enemy_type {
+            EnemyType::Skeleton => {
+                spawn_enemy_projectile(&mut commands, EnemyProjectileKind::Arrow, event.origin, direction, stats.damage, event.attacker);
+            }
+            EnemyType::Orc => {
+                // Ground slam hits everything around the orc, not just what it faces
+                if event.origin.distance(player_pos) <= SLAM_RADIUS {
+                    combat_events.write(hit_player(DamageType::Physical));
+                }
+                commands.spawn((
+                    SlamEffect {
+                        lifetime: Timer::from_seconds(0.3, TimerMode::Once),
+                    },
+                    Sprite {
+                        color: Color::srgba(0.6, 0.45, 0.2, 0.5),
+                        custom_size: Some(Vec2::splat(SLAM_RADIUS * 2.0)),
+                        ..default()
+                    },
+                    Transform::from_translation(event.origin.extend(1.0)).with_scale(Vec3::splat(0.2)),
+                ));
+            }
+            EnemyType::DarkKnight => {
+                commands.entity(event.attacker).try_insert(Charging {
+                    direction,
+                    timer: Timer::from_seconds(CHARGE_DURATION, TimerMode::Once),
+                    has_hit: false,
+                });
+            }
+            EnemyType::Necromancer => {
+                // Raise the nearest corpse, else top up the goblin escort, else fire a bolt
+                let corpse = corpse_q.iter()
+                    .map(|(entity, tf)| (entity, tf.translation.truncate()))
+                    .filter(|(_, pos)| pos.distance(event.origin) <= RAISE_RANGE)
+                    .min_by(|a, b| a.1.distance(event.origin).total_cmp(&b.1.distance(event.origin)));
+                let escort = enemy_q.iter()
+                    .filter(|(tf, enemy)| {
+                        enemy.enemy_type == EnemyType::Goblin
+                            && tf.translation.truncate().distance(event.origin) <= RAISE_RANGE
+                    })
+                    .count();
+
+                if let Some((corpse_entity, corpse_pos)) = corpse {
+                    commands.entity(corpse_entity).try_despawn();
+                    spawn_events.write(SpawnEnemyEvent {
+                        position: corpse_pos.extend(1.0),
+                        enemy_id: EnemyType::Skeleton.id().to_string(),
+                        modifiers: EnemyModifiers::default(),
+                    });
+                } else if escort < MAX_SUMMONED_GOBLINS {
+                    for side in [-1.0, 1.0] {
+                        spawn_events.write(SpawnEnemyEvent {
+                            position: (event.origin + direction.perp() * 30.0 * side).extend(1.0),
+                            enemy_id: EnemyType::Goblin.id().to_string(),
+                            modifiers: EnemyModifiers::default(),
+                        });
+                    }
+                } else {
+                    spawn_enemy_projectile(&mut commands, EnemyProjectileKind::DarkBolt, event.origin, direction, stats.damage, event.attacker);
+                }
+            }
+            _ => {
+                // Melee: stepping out of reach during the wind-up dodges the hit
+                if event.origin.distance(player_pos) <= attack_range(event.enemy_type) * 1.25 {
+                    combat_events.write(hit_player(DamageType::Physical));
+                }
+            }
+        }
+    }
+}
+
+fn update_charges(
+    mut commands: Commands,
+    mut charger_q: Query<(Entity, &mut Charging, &mut Enemy, &Transform, &mut Velocity, &mut FacingDirection), Without<Player>>,
+    player_q: Query<(Entity, &Transform), With<Player>>,
+    grid: Res<TileGrid>,
+    mut combat_events: EventWriter<CombatEvent>,
+    attacker_q: Query<&CombatStats>,
+    time: Res<Time>,
+) {
+    let player = player_q.single().ok();
+
+    for (entity, mut charge, mut enemy, transform, mut velocity, mut facing) in charger_q.iter_mut() {
+        charge.timer.tick(time.delta());
+        velocity.0 = charge.direction * CHARGE_SPEED;
+        if charge.direction.x != 0.0 {
+            *facing = if charge.direction.x < 0.0 { FacingDirection::Left } else { FacingDirection::Right };
+        }
+
+        // One hit per charge
+        if let Some((player_entity, player_tf)) = player {
+            if !charge.has_hit && transform.translation.distance(player_tf.translation) <= 36.0 {
+                charge.has_hit = true;
+                if let Ok(stats) = attacker_q.get(entity) {
+                    combat_events.write(CombatEvent {
+                        attacker: entity,
+                        target: player_entity,
+                        damage: stats.damage * 2,
+                        damage_type: DamageType::Physical,
+                        position: player_tf.translation,
+                        kind: HitKind::Direct,
+                    });
+                }
+            }
+        }
+
+        // Charges end early against walls
+        let ahead = transform.translation.truncate() + charge.direction * grid.tile_size * 0.6;
+        if charge.timer.finished() || !grid.is_walkable(ahead) {
+            velocity.0 = Vec2::ZERO;
+            let cooldown = attack_cooldown(enemy.enemy_type);
+            enemy.set_state(AIState::Chasing, cooldown);
+            commands.entity(entity).remove::<Charging>();
+        }
+    }
+}
+
+fn update_enemy_projectiles(
+    mut commands: Commands,
+    mut projectile_q: Query<(Entity, &Transform, &mut EnemyProjectile)>,
+    player_q: Query<(Entity, &Transform), With<Player>>,
+    grid: Res<TileGrid>,
+    mut combat_events: EventWriter<CombatEvent>,
+    time: Res<Time>,
+) {
+    let player = player_q.single().ok();
+
+    for (entity, transform, mut projectile) in projectile_q.iter_mut() {
+        projectile.lifetime.tick(time.delta());
+        let position = transform.translation.truncate();
+
+        if let Some((player_entity, player_tf)) = player {
+            if position.distance(player_tf.translation.truncate()) <= projectile.radius + 14.0 {
+                combat_events.write(CombatEvent {
+                    attacker: projectile.owner,
+                    target: player_entity,
+                    damage: projectile.damage,
+                    damage_type: projectile.damage_type,
+                    position: player_tf.translation,
+                    kind: HitKind::Direct,
+                });
+                commands.entity(entity).despawn();
+                continue;
+            }
+        }
+
+        if projectile.lifetime.finished() || !grid.is_walkable(position) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_corpses(
+    mut commands: Commands,
+    mut corpse_q: Query<(Entity, &mut Corpse)>,
+    time: Res<Time>,
+) {
+    for (entity, mut corpse) in corpse_q.iter_mut() {
+        corpse.lifetime.tick(time.delta());
+        if corpse.lifetime.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_slam_effects(
+    mut commands: Commands,
+    mut effect_q: Query<(Entity, &mut SlamEffect, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (entity, mut effect, mut transform, mut sprite) in effect_q.iter_mut() {
+        effect.lifetime.tick(time.delta());
+        if effect.lifetime.finished() {
+            commands.entity(entity).despawn();
+        } else {
+            let progress = effect.lifetime.fraction();
+            transform.scale = Vec3::splat(0.2 + progress * 0.8);
+            sprite.color.set_alpha((1.0 - progress) * 0.5);
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use bevy::prelude::*;
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use crate::game::animation::{AnimationController, AnimationClip};
+use crate::game::enemy::{EnemyType, EnemyAssets, EnemyAtlas};
+use crate::game::combat::damage::Resistances;
+
+pub const ENEMY_ROSTER: &str = "data/enemies.json";
+
+pub struct EnemyDefinitionPlugin;
+
+impl Plugin for EnemyDefinitionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<EnemyRoster>()
+            .init_asset_loader::<EnemyRosterLoader>()
+            .add_systems(Startup, load_enemy_definitions)
+            .add_systems(Update, rebuild_enemy_atlases);
+    }
+}
+
+/// Handle to the roster every spawn is resolved against
+#[derive(Resource)]
+pub struct EnemyDefinitions {
+    pub handle: Handle<EnemyRoster>,
+}
+
+/// Every enemy the game can spawn, loaded from `assets/data/enemies.json`.
+/// The file is watched when built with `--features hot_reload`; changes apply to the next spawns.
+#[derive(Asset, TypePath, Clone)]
+pub struct EnemyRoster {
+    pub enemies: Vec<EnemyDefinition>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct EnemyDefinition {
+    pub id: String,
+    pub name: String,
+    /// Decides the AI, attacks and (for bosses) the phase table
+    pub archetype: EnemyType,
+    pub health: i32,
+    pub damage: i32,
+    #[serde(default)]
+    pub armor: i32,
+    #[serde(default)]
+    pub resistances: Resistances,
+    pub speed: f32,
+    #[serde(default = "default_detection_range")]
+    pub detection_range: f32,
+    #[serde(default = "default_crit_chance")]
+    pub crit_chance: f32,
+    #[serde(default = "default_crit_multiplier")]
+    pub crit_multiplier: f32,
+    pub collider: [f32; 2],
+    pub sprite: SpriteDefinition,
+    pub animations: HashMap<String, AnimationDefinition>,
+    #[serde(default)]
+    pub loot_table: Option<String>,
+    #[serde(default)]
+    pub xp: u32,
+    #[serde(default)]
+    pub spawn: SpawnRules,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct SpriteDefinition {
+    pub sheet: String,
+    pub frame_size: [u32; 2],
+    pub columns: u32,
+    pub rows: u32,
+    pub size: f32,
+    #[serde(default)]
+    pub tint: Option<[f32; 3]>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct AnimationDefinition {
+    pub first: usize,
+    pub last: usize,
+    pub frame_time: f32,
+    #[serde(default = "default_looping")]
+    pub looping: bool,
+}
+
+/// When wave spawning may pick this enemy. Bosses use `min_wave` as the first boss wave they appear on.
+#[derive(Deserialize, Clone, Copy)]
+pub struct SpawnRules {
+    #[serde(default = "default_min_wave")]
+    pub min_wave: u32,
+    #[serde(default)]
+    pub max_wave: Option<u32>,
+    #[serde(default)]
+    pub weight: f32, // 0 keeps the enemy out of waves; it can still be summoned
+}
+
+impl Default for SpawnRules {
+    fn default() -> Self {
+        Self {
+            min_wave: default_min_wave(),
+            max_wave: None,
+            weight: 0.0,
+        }
+    }
+}
+
+fn default_detection_range() -> f32 { 300.0 }
+fn default_crit_chance() -> f32 { 0.1 }
+fn default_crit_multiplier() -> f32 { 1.5 }
+fn default_looping() -> bool { true }
+fn default_min_wave() -> u32 { 1 }
+
+/// On-disk representation of the roster file
+#[derive(Deserialize)]
+struct EnemyRosterFile {
+    enemies: Vec<EnemyDefinition>,
+}
+
+#[derive(Debug)]
+pub enum EnemyDefinitionError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    DuplicateId(String),
+    Invalid { id: String, reason: String },
+}
+
+impl fmt::Display for EnemyDefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnemyDefinitionError::Io(e) => write!(f, "could not read enemy definitions: {}", e),
+            EnemyDefinitionError::Json(e) => write!(f, "invalid enemy definition json: {}", e),
+            EnemyDefinitionError::DuplicateId(id) => write!(f, "enemy '{}' is defined more than once", id),
+            EnemyDefinitionError::Invalid { id, reason } => write!(f, "enemy '{}': {}", id, reason),
+        }
+    }
+}
+
+impl std::error::Error for EnemyDefinitionError {}
+
+impl From<std::io::Error> for EnemyDefinitionError {
+    fn from(e: std::io::Error) -> Self {
+        EnemyDefinitionError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for EnemyDefinitionError {
+    fn from(e: serde_json::Error) -> Self {
+        EnemyDefinitionError::Json(e)
+    }
+}
+
+impl EnemyRoster {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EnemyDefinitionError> {
+        let file: EnemyRosterFile = serde_json::from_slice(bytes)?;
+
+        let mut ids = HashSet::new();
+        for definition in &file.enemies {
+            if !ids.insert(definition.id.as_str()) {
+                return Err(EnemyDefinitionError::DuplicateId(definition.id.clone()));
+            }
+            definition.validate()?;
+        }
+
+        Ok(Self { enemies: file.enemies })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&EnemyDefinition> {
+        self.enemies.iter().find(|definition| definition.id == id)
+    }
+
+    /// Regular enemies allowed on this wave, with their spawn weights
+    pub fn wave_candidates(&self, wave: u32) -> impl Iterator<Item = &EnemyDefinition> {
+        self.enemies.iter().filter(move |definition| {
+            !definition.archetype.is_boss()
+                && definition.spawn.weight > 0.0
+                && definition.spawn.allows(wave)
+        })
+    }
+
+    /// The boss with the highest `min_wave` that has been reached
+    pub fn boss_for_wave(&self, wave: u32) -> Option<&EnemyDefinition> {
+        self.enemies.iter()
+            .filter(|definition| definition.archetype.is_boss() && definition.spawn.allows(wave))
+            .max_by_key(|definition| definition.spawn.min_wave)
+    }
+}
+
+impl SpawnRules {
+    pub fn allows(&self, wave: u32) -> bool {
+        wave >= self.min_wave && self.max_wave.is_none_or(|max| wave <= max)
+    }
+}
+
+impl EnemyDefinition {
+    fn validate(&self) -> Result<(), EnemyDefinitionError> {
+        let invalid = |reason: String| Err(EnemyDefinitionError::Invalid { id: self.id.clone(), reason });
+
+        if self.health <= 0 {
+            return invalid(format!("health must be positive, got {}", self.health));
+        }
+        if self.speed < 0.0 {
+            return invalid(format!("speed can't be negative, got {}", self.speed));
+        }
+        if self.sprite.columns == 0 || self.sprite.rows == 0 {
+            return invalid("sprite sheet needs at least one row and column".to_string());
+        }
+        if !self.animations.contains_key("idle") {
+            return invalid("missing an 'idle' animation".to_string());
+        }
+
+        let frames = (self.sprite.columns * self.sprite.rows) as usize;
+        for (name, animation) in &self.animations {
+            if animation.first > animation.last || animation.last >= frames {
+                return invalid(format!(
+                    "animation '{}' uses frames {}-{}, the sheet has {}",
+                    name, animation.first, animation.last, frames,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn tint(&self) -> Color {
+        match self.sprite.tint {
+            Some([r, g, b]) => Color::linear_rgb(r, g, b),
+            None => Color::WHITE,
+        }
+    }
+
+    pub fn animation_controller(&self) -> AnimationController {
+        let mut anim = AnimationController::new();
+        for (name, animation) in &self.animations {
+            anim.add_animation(name, AnimationClip::new(
+                animation.first,
+                animation.last,
+                animation.frame_time,
+                animation.looping,
+            ));
+        }
+        anim.play("idle");
+        anim
+    }
+}
+
+#[derive(Default)]
+pub struct EnemyRosterLoader;
+
+impl AssetLoader for EnemyRosterLoader {
+    type Asset = EnemyRoster;
+    type Settings = ();
+    type Error = EnemyDefinitionError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        EnemyRoster::from_bytes(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
+
+fn load_enemy_definitions(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(EnemyDefinitions {
+        handle: asset_server.load(ENEMY_ROSTER),
+    });
+}
+
+/// Builds a sprite atlas per definition whenever the roster loads or changes on disk
+fn rebuild_enemy_atlases(
+    mut roster_events: EventReader<AssetEvent<EnemyRoster>>,
+    definitions: Option<Res<EnemyDefinitions>>,
+    rosters: Res<Assets<EnemyRoster>>,
+    asset_server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut enemy_assets: ResMut<EnemyAssets>,
+) {
+    let Some(definitions) = definitions else { return };
+    let changed = roster_events.read().any(|event| {
+        event.is_loaded_with_dependencies(&definitions.handle) || event.is_modified(&definitions.handle)
+    });
+    if !changed {
+        return;
+    }
+    let Some(roster) = rosters.get(&definitions.handle) else { return };
+
+    enemy_assets.atlases.clear();
+    for definition in &roster.enemies {
+        let sprite = &definition.sprite;
+        enemy_assets.atlases.insert(definition.id.clone(), EnemyAtlas {
+            texture: asset_server.load(&sprite.sheet),
+            layout: layouts.add(TextureAtlasLayout::from_grid(
+                UVec2::from(sprite.frame_size),
+                sprite.columns, sprite.rows,
+                None, None,
+            )),
+        });
+    }
+    println!("Loaded {} enemy definitions", roster.enemies.len());
+}
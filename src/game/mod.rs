@@ -1,7 +1,12 @@
 // Game module - Contains all gameplay-related systems and components
 
 pub mod player;          // Player entity and movement systems
+pub mod player_actions;  // Buffered attack, dash and manual ability casting
 pub mod enemy;           // Enemy AI and spawning
+pub mod enemy_archetypes; // Per-archetype attacks: arrows, charges, slams, summons
+pub mod boss;            // Boss phase tables, attack patterns and enrage
+pub mod elite;           // Elite and champion affixes
+pub mod enemy_definitions; // Enemy stats, sprites and spawn rules loaded from assets/data/enemies.json
 pub mod collectible;     // Fruit and coin collection
 pub mod combat;          // Combat mechanics and damage
 pub mod movement;        // Movement utilities
@@ -23,8 +28,15 @@ impl Plugin for GamePlugin {
         app
             .add_plugins((
                 player::PlayerPlugin,
+                player_actions::PlayerActionsPlugin,
                 player_visual::PlayerVisualPlugin,
-                enemy::EnemyPlugin,
+                (
+                    enemy_definitions::EnemyDefinitionPlugin,
+                    enemy::EnemyPlugin,
+                    enemy_archetypes::EnemyArchetypePlugin,
+                    boss::BossPlugin,
+                    elite::ElitePlugin,
+                ),
                 collectible::CollectiblePlugin,
                 combat::CombatPlugin,
                 movement::MovementPlugin,
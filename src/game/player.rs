@@ -3,6 +3,8 @@ use crate::game::animation::{AnimationController, AnimationClip};
 use crate::entities::powerup::PowerUpSlots;
 use crate::game::movement::{Velocity, Collider};
 use crate::game::combat::{Health, CombatStats};
+use crate::game::combat::effects::StatusEffects;
+use crate::game::combat::modifiers::StatModifiers;
 use crate::systems::talents::PlayerTalents;
 use crate::game::player_visual::PlayerParts;
 use crate::game::abilities::ActiveAbilities;
@@ -29,7 +31,6 @@ impl Plugin for PlayerPlugin {
             .add_systems(Update, (
                 player_input_system,
                 update_player_stats,
-                apply_speed_buffs,
                 update_player_sprite_direction.after(crate::game::animation::update_animations),
             ));
     }
@@ -66,7 +67,11 @@ pub struct PlayerController {
     pub move_speed: f32,
     pub dash_speed: f32,
     pub dash_cooldown: Timer,
+    pub dash_timer: Timer,
+    pub dash_direction: Vec2,
     pub is_dashing: bool,
+    pub attack_cooldown: Timer,
+    pub aim: Vec2, // Last movement direction; attacks and dashes go this way
 }
 
 #[derive(Resource, Default)]
@@ -94,14 +99,23 @@ impl Default for PlayerController {
         Self {
             move_speed: 200.0,
             dash_speed: 500.0,
-            dash_cooldown: Timer::from_seconds(2.0, TimerMode::Once),
+            dash_cooldown: ready_timer(2.0),
+            dash_timer: Timer::from_seconds(0.15, TimerMode::Once),
+            dash_direction: Vec2::ZERO,
             is_dashing: false,
+            attack_cooldown: ready_timer(0.35),
+            aim: Vec2::NEG_X,
         }
     }
 }
 
 
-
+/// A cooldown that starts out already finished
+fn ready_timer(seconds: f32) -> Timer {
+    let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+    timer.tick(timer.duration());
+    timer
+}
 
 fn spawn_player(
     mut commands: Commands,
@@ -136,6 +150,7 @@ fn spawn_player(
             crit_chance: 0.1,
             crit_multiplier: 2.0,
         },
+        (StatusEffects::default(), StatModifiers::default()),
         Velocity(Vec2::ZERO),
         Collider { size: Vec2::splat(28.0) },
         FacingDirection::default(),
@@ -154,12 +169,17 @@ fn spawn_player(
 }
 
 /// System to handle player input and movement
-fn player_input_system(
-    mut player_q: Query<(&mut Velocity, &mut AnimationController, &PlayerController, &mut FacingDirection), With<Player>>,
+pub fn player_input_system(
+    mut player_q: Query<(&mut Velocity, &mut AnimationController, &mut PlayerController, &mut FacingDirection, &StatusEffects, &StatModifiers), With<Player>>,
     keys: Res<ButtonInput<KeyCode>>,
     _time: Res<Time>,
 ) {
-    let Ok((mut velocity, mut anim, controller, mut facing)) = player_q.single_mut() else { return };
+    let Ok((mut velocity, mut anim, mut controller, mut facing, statuses, modifiers)) = player_q.single_mut() else { return };
+    
+    // Dashes steer themselves
+    if controller.is_dashing {
+        return;
+    }
     
     let mut movement = Vec2::ZERO;
     
@@ -178,7 +198,8 @@ fn player_input_system(
     // Normalize diagonal movement
     if movement.length() > 0.0 {
         movement = movement.normalize();
-        velocity.0 = movement * controller.move_speed;
+        controller.aim = movement;
+        velocity.0 = movement * controller.move_speed * statuses.speed_multiplier() * modifiers.effective.speed_multiplier;
         
         // Play walk animation
         if anim.current != "walk" {
@@ -192,14 +213,6 @@ fn player_input_system(
             anim.play("idle");
         }
     }
-    
-    // Handle dash
-    if keys.just_pressed(KeyCode::ShiftLeft) && !controller.is_dashing {
-        if movement.length() > 0.0 {
-            velocity.0 = movement * controller.dash_speed;
-            anim.play("dash");
-        }
-    }
 }
 
 fn update_player_stats(
@@ -224,36 +237,6 @@ fn update_player_stats(
     }
 }
 
-#[derive(Component)]
-pub struct SpeedBuff {
-    pub multiplier: f32,
-    pub duration: Timer,
-}
-
-fn apply_speed_buffs(
-    mut commands: Commands,
-    mut player_q: Query<(Entity, &mut PlayerController, Option<&mut SpeedBuff>), With<Player>>,
-    time: Res<Time>,
-) {
-    for (entity, mut controller, speed_buff) in player_q.iter_mut() {
-        if let Some(mut buff) = speed_buff {
-            buff.duration.tick(time.delta());
-            
-            if buff.duration.finished() {
-                // Remove expired buff and reset speed
-                commands.entity(entity).remove::<SpeedBuff>();
-                controller.move_speed = 200.0; // Reset to base speed
-            } else {
-                // Apply speed multiplier
-                controller.move_speed = 200.0 * buff.multiplier;
-            }
-        } else {
-            // No buff active, ensure base speed
-            controller.move_speed = 200.0;
-        }
-    }
-}
-
 /// System to update player sprite based on facing direction
 fn update_player_sprite_direction(
     mut player_q: Query<(&FacingDirection, &mut Sprite, &AnimationController), With<Player>>,
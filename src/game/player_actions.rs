@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+use crate::core::events::{CombatEvent, HitKind, DamageType};
+use crate::core::input::{Action, InputBuffer, buffer_input_system};
+use crate::entities::powerup::PowerUpSlots;
+use crate::game::animation::AnimationController;
+use crate::game::combat::{CombatStats, DamageImmunity};
+use crate::game::combat::effects::StatusEffects;
+use crate::game::enemy::Enemy;
+use crate::game::movement::Velocity;
+use crate::game::player::{self, Player, PlayerController, FacingDirection};
+use crate::systems::combo::ComboEvent;
+use crate::world::tile_grid::TileGrid;
+
+const ATTACK_RANGE: f32 = 48.0;
+const ATTACK_ARC: f32 = 0.35;        // Minimum dot product between aim and target, roughly 140 degrees wide
+const SWING_LIFETIME: f32 = 0.12;
+const DASH_IFRAMES: f32 = 0.25;      // Slightly longer than the dash itself
+
+pub struct PlayerActionsPlugin;
+
+impl Plugin for PlayerActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            player_attack,
+            player_dash,
+            fade_attack_swings,
+            toggle_slot_locks,
+        ).after(buffer_input_system).after(player::player_input_system));
+    }
+}
+
+/// Short-lived slash drawn in front of the player
+#[derive(Component)]
+pub struct AttackSwing {
+    pub lifetime: Timer,
+}
+
+/// Swings at every enemy in a cone along the player's aim
+fn player_attack(
+    mut commands: Commands,
+    mut player_q: Query<(Entity, &Transform, &mut PlayerController, &mut FacingDirection, &mut AnimationController, &CombatStats, &StatusEffects), With<Player>>,
+    enemy_q: Query<(Entity, &Transform), (With<Enemy>, Without<Player>)>,
+    mut buffer: ResMut<InputBuffer>,
+    mut combat_events: EventWriter<CombatEvent>,
+    mut combo_events: EventWriter<ComboEvent>,
+    time: Res<Time>,
+) {
+    let Ok((player_entity, player_tf, mut controller, mut facing, mut anim, stats, statuses)) = player_q.single_mut() else { return };
+    controller.attack_cooldown.tick(time.delta());
+
+    // Presses during the cooldown stay buffered and fire as soon as it ends
+    if !controller.attack_cooldown.finished() || controller.is_dashing || statuses.is_disabled() {
+        return;
+    }
+    if !buffer.consume(Action::Attack, time.elapsed_secs()) {
+        return;
+    }
+    controller.attack_cooldown.reset();
+
+    let aim = controller.aim;
+    if aim.x != 0.0 {
+        *facing = if aim.x < 0.0 { FacingDirection::Left } else { FacingDirection::Right };
+    }
+    anim.play("attack");
+
+    let origin = player_tf.translation.truncate();
+    for (enemy_entity, enemy_tf) in enemy_q.iter() {
+        let offset = enemy_tf.translation.truncate() - origin;
+        if offset.length() <= ATTACK_RANGE && offset.normalize_or_zero().dot(aim) >= ATTACK_ARC {
+            combat_events.write(CombatEvent {
+                attacker: player_entity,
+                target: enemy_entity,
+                damage: stats.damage,
+                damage_type: DamageType::Physical,
+                position: enemy_tf.translation,
+                kind: HitKind::Direct,
+            });
+            combo_events.write(ComboEvent::Hit);
+        }
+    }
+
+    commands.spawn((
+        AttackSwing {
+            lifetime: Timer::from_seconds(SWING_LIFETIME, TimerMode::Once),
+        },
+        Sprite {
+            color: Color::srgba(1.0, 1.0, 1.0, 0.6),
+            custom_size: Some(Vec2::new(12.0, ATTACK_RANGE * 1.2)),
+            ..default()
+        },
+        Transform::from_translation((origin + aim * ATTACK_RANGE * 0.6).extend(player_tf.translation.z + 1.0))
+            .with_rotation(Quat::from_rotation_z(aim.to_angle())),
+    ));
+}
+
+fn fade_attack_swings(
+    mut commands: Commands,
+    mut swing_q: Query<(Entity, &mut AttackSwing, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (entity, mut swing, mut sprite) in swing_q.iter_mut() {
+        swing.lifetime.tick(time.delta());
+        if swing.lifetime.finished() {
+            commands.entity(entity).despawn();
+        } else {
+            sprite.color.set_alpha(0.6 * swing.lifetime.fraction_remaining());
+        }
+    }
+}
+
+/// Bursts along the aim with brief invulnerability, stopping short of walls
+fn player_dash(
+    mut commands: Commands,
+    mut player_q: Query<(Entity, &Transform, &mut PlayerController, &mut Velocity, &mut AnimationController, &StatusEffects, Has<DamageImmunity>), With<Player>>,
+    mut buffer: ResMut<InputBuffer>,
+    grid: Res<TileGrid>,
+    time: Res<Time>,
+) {
+    let Ok((entity, transform, mut controller, mut velocity, mut anim, statuses, immune)) = player_q.single_mut() else { return };
+    controller.dash_cooldown.tick(time.delta());
+
+    if controller.is_dashing {
+        controller.dash_timer.tick(time.delta());
+        let ahead = transform.translation.truncate() + controller.dash_direction * grid.tile_size * 0.6;
+        if controller.dash_timer.finished() || !grid.is_walkable(ahead) {
+            controller.is_dashing = false;
+            velocity.0 = Vec2::ZERO;
+        } else {
+            velocity.0 = controller.dash_direction * controller.dash_speed;
+        }
+        return;
+    }
+
+    if !controller.dash_cooldown.finished() || statuses.is_disabled() {
+        return;
+    }
+    if !buffer.consume(Action::Dash, time.elapsed_secs()) {
+        return;
+    }
+
+    controller.is_dashing = true;
+    controller.dash_direction = controller.aim;
+    controller.dash_timer.reset();
+    controller.dash_cooldown.reset();
+    velocity.0 = controller.dash_direction * controller.dash_speed;
+    anim.play("dash");
+
+    if !immune {
+        commands.entity(entity).insert(DamageImmunity {
+            timer: Timer::from_seconds(DASH_IFRAMES, TimerMode::Once),
+        });
+    }
+}
+
+/// Locked fruit stays put when new fruit would push it out
+fn toggle_slot_locks(
+    mut player_q: Query<&mut PowerUpSlots, With<Player>>,
+    mut buffer: ResMut<InputBuffer>,
+    time: Res<Time>,
+) {
+    let Ok(mut slots) = player_q.single_mut() else { return };
+    for slot in 0..3 {
+        if !buffer.consume(Action::ToggleLock(slot), time.elapsed_secs()) {
+            continue;
+        }
+        if let Some(locked) = slots.toggle_lock(slot as usize) {
+            println!("Fruit slot {} {}", slot + 1, if locked { "locked" } else { "unlocked" });
+        }
+    }
+}
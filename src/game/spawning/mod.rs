@@ -1,151 +1,234 @@
+pub mod wave_script;
+
 use bevy::prelude::*;
 use rand::Rng;
-use crate::game::enemy::{SpawnEnemyEvent, SpawnBossEvent, EnemyType};
-use crate::core::state::GameState;
+use crate::game::enemy::{SpawnEnemyEvent, SpawnBossEvent};
+use crate::game::enemy_definitions::{EnemyDefinitions, EnemyRoster};
+use crate::game::combat::Health;
+use crate::game::player::Player;
+use crate::world::spawn_placement::SpawnPlacement;
+use crate::core::state::{GameState, PlayState};
+use crate::core::events::{GameEvent, GameEventType, HealEvent};
+use wave_script::{WaveScript, WaveScriptLoader, WavePlan, WaveAction, BossPosition, WAVE_SCRIPT, pattern_positions};
 
 pub struct SpawningPlugin;
 
 impl Plugin for SpawningPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_asset::<WaveScript>()
+            .init_asset_loader::<WaveScriptLoader>()
             .init_resource::<WaveManager>()
-            .init_resource::<EnemySpawnTimer>()
+            .add_systems(Startup, load_wave_script)
             .add_systems(Update, (
-                spawn_wave_system.run_if(in_state(GameState::Playing)),
+                spawn_wave_system.run_if(in_state(GameState::Playing)).run_if(not(in_state(PlayState::Transitioning))),
                 update_difficulty.run_if(in_state(GameState::Playing)),
-                spawn_collectibles.run_if(in_state(GameState::Playing)),
+                handle_boss_defeated,
+                spawn_collectibles.run_if(in_state(GameState::Playing)).run_if(not(in_state(PlayState::Transitioning))),
             ));
     }
 }
 
+/// Handle to the wave script driving `WaveManager`
+#[derive(Resource)]
+pub struct WaveScriptHandle(pub Handle<WaveScript>);
+
 #[derive(Resource)]
 pub struct WaveManager {
     pub current_wave: u32,
     pub enemies_spawned: u32,
     pub enemies_alive: u32,
-    pub wave_timer: Timer,
     pub difficulty_multiplier: f32,
     pub boss_spawned: bool,
     pub wave_complete: bool,
+    pub plan: WavePlan,          // The running wave, groups and events sorted by time
+    pub wave_time: f32,          // Seconds since the wave started
+    pub intermission: Timer,     // Pause between a cleared wave and the next
+    next_group: usize,
+    next_event: usize,
+    boss_pending: bool,
 }
 
-#[derive(Resource)]
-pub struct EnemySpawnTimer(pub Timer);
-
 impl Default for WaveManager {
     fn default() -> Self {
         Self {
-            current_wave: 1,
+            current_wave: 0,
             enemies_spawned: 0,
             enemies_alive: 0,
-            wave_timer: Timer::from_seconds(30.0, TimerMode::Once),
             difficulty_multiplier: 1.0,
             boss_spawned: false,
             wave_complete: false,
+            plan: WavePlan::default(),
+            wave_time: 0.0,
+            intermission: Timer::from_seconds(3.0, TimerMode::Once),
+            next_group: 0,
+            next_event: 0,
+            boss_pending: false,
         }
     }
 }
 
-impl Default for EnemySpawnTimer {
-    fn default() -> Self {
-        Self(Timer::from_seconds(2.0, TimerMode::Repeating))
+impl WaveManager {
+    /// Enemies the current plan spawns in total, not counting the boss
+    pub fn planned_enemies(&self) -> u32 {
+        self.plan.groups.iter().map(|group| group.count).sum()
     }
+
+    fn start_wave(&mut self, wave: u32, mut plan: WavePlan) {
+        plan.groups.sort_by(|a, b| a.at.total_cmp(&b.at));
+        plan.events.sort_by(|a, b| a.at.total_cmp(&b.at));
+
+        self.current_wave = wave;
+        self.enemies_spawned = 0;
+        self.wave_complete = false;
+        self.boss_spawned = false;
+        self.boss_pending = plan.boss.is_some();
+        self.wave_time = 0.0;
+        self.next_group = 0;
+        self.next_event = 0;
+        self.plan = plan;
+        println!("Wave {} started: {} enemies", wave, self.planned_enemies());
+    }
+
+    fn finished_spawning(&self) -> bool {
+        self.next_group >= self.plan.groups.len() && !self.boss_pending
+    }
+}
+
+fn load_wave_script(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(WaveScriptHandle(asset_server.load(WAVE_SCRIPT)));
 }
 
 fn spawn_wave_system(
     mut wave_manager: ResMut<WaveManager>,
-    mut spawn_timer: ResMut<EnemySpawnTimer>,
     mut spawn_events: EventWriter<SpawnEnemyEvent>,
     mut boss_events: EventWriter<SpawnBossEvent>,
-    player_q: Query<&Transform, With<crate::game::player::Player>>,
+    mut heal_events: EventWriter<HealEvent>,
+    mut player_q: Query<(Entity, &Transform, &mut Health), With<Player>>,
     enemy_q: Query<&Transform, With<crate::game::enemy::Enemy>>,
+    placement: SpawnPlacement,
+    script: Option<Res<WaveScriptHandle>>,
+    scripts: Res<Assets<WaveScript>>,
+    definitions: Option<Res<EnemyDefinitions>>,
+    rosters: Res<Assets<EnemyRoster>>,
     time: Res<Time>,
 ) {
-    wave_manager.wave_timer.tick(time.delta());
-    spawn_timer.0.tick(time.delta());
+    // Nothing can spawn until the wave script and enemy definitions have loaded
+    let Some(script) = script.and_then(|script| scripts.get(&script.0)) else { return };
+    let Some(roster) = definitions.and_then(|definitions| rosters.get(&definitions.handle)) else { return };
+    let Ok((player_entity, player_tf, mut player_health)) = player_q.single_mut() else { return };
+    let player_pos = player_tf.translation.truncate();
     
     // Update alive enemy count
     wave_manager.enemies_alive = enemy_q.iter().count() as u32;
     
-    // Check if wave is complete
-    if wave_manager.enemies_spawned >= calculate_wave_enemies(wave_manager.current_wave) 
-        && wave_manager.enemies_alive == 0 
-        && !wave_manager.boss_spawned {
-        wave_manager.wave_complete = true;
+    if wave_manager.current_wave == 0 {
+        wave_manager.start_wave(1, script.plan(1, roster));
+        return;
+    }
+    
+    // A cleared wave stays complete for the intermission, then the next one starts
+    if wave_manager.wave_complete {
+        wave_manager.intermission.tick(time.delta());
+        if wave_manager.intermission.finished() {
+            let next = wave_manager.current_wave + 1;
+            wave_manager.start_wave(next, script.plan(next, roster));
+        }
+        return;
     }
     
-    // Start new wave
-    if wave_manager.wave_complete || (wave_manager.current_wave == 1 && wave_manager.enemies_spawned == 0) {
-        wave_manager.current_wave += 1;
-        wave_manager.enemies_spawned = 0;
-        wave_manager.wave_complete = false;
-        wave_manager.boss_spawned = false;
-        wave_manager.wave_timer.reset();
-        
-        // Boss wave every 5 waves
-        if wave_manager.current_wave % 5 == 0 {
-            wave_manager.boss_spawned = true;
-            if let Ok(player_tf) = player_q.single() {
-                boss_events.send(SpawnBossEvent {
-                    position: player_tf.translation + Vec3::new(300.0, 0.0, 3.0),
-                    boss_type: choose_boss_type(wave_manager.current_wave),
+    wave_manager.wave_time += time.delta_secs();
+    let wave_time = wave_manager.wave_time;
+    let mut rng = rand::thread_rng();
+    
+    // Spawn every group whose time has come, moved onto open floor off-screen
+    let view_half = placement.view_rect().half_size();
+    let spawn_points = placement.off_screen_spawn_points();
+    while let Some(group) = wave_manager.plan.groups.get(wave_manager.next_group).filter(|group| group.at <= wave_time).cloned() {
+        for desired in pattern_positions(group.pattern, player_pos, group.count, view_half, &spawn_points, &mut rng) {
+            let Some(position) = placement.enemy_position(desired, &mut rng) else {
+                println!("No room to spawn {} near ({:.0}, {:.0})", group.enemy, desired.x, desired.y);
+                continue;
+            };
+            spawn_events.write(SpawnEnemyEvent {
+                position: position.extend(3.0),
+                enemy_id: group.enemy.clone(),
+                modifiers: group.modifiers,
+            });
+        }
+        wave_manager.enemies_spawned += group.count;
+        wave_manager.next_group += 1;
+    }
+    
+    // Timed events
+    while let Some(event) = wave_manager.plan.events.get(wave_manager.next_event).filter(|event| event.at <= wave_time).cloned() {
+        match event.action {
+            WaveAction::Announce { text } => println!("{}", text),
+            WaveAction::HealPlayer { amount } => {
+                player_health.heal(amount);
+                heal_events.write(HealEvent {
+                    target: player_entity,
+                    amount,
+                    position: player_tf.translation,
                 });
             }
         }
+        wave_manager.next_event += 1;
     }
     
-    // Spawn regular enemies
-    if spawn_timer.0.just_finished() && 
-       wave_manager.enemies_spawned < calculate_wave_enemies(wave_manager.current_wave) {
-        if let Ok(player_tf) = player_q.single() {
-            let mut rng = rand::thread_rng();
-            let spawn_count = 1u32.max((3.0 * wave_manager.difficulty_multiplier) as u32);
-            
-            for _ in 0..spawn_count {
-                if wave_manager.enemies_spawned >= calculate_wave_enemies(wave_manager.current_wave) {
-                    break;
+    // Boss placement
+    if wave_manager.boss_pending {
+        if let Some(boss) = wave_manager.plan.boss.clone().filter(|boss| boss.at <= wave_time) {
+            let position = match boss.placement {
+                BossPosition::Offset { x, y } => player_pos + Vec2::new(x, y),
+                BossPosition::Around { distance } => {
+                    player_pos + Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU)) * distance
                 }
-                
-                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
-                let distance = rng.gen_range(200.0..400.0);
-                let spawn_pos = Vec3::new(
-                    player_tf.translation.x + angle.cos() * distance,
-                    player_tf.translation.y + angle.sin() * distance,
-                    3.0,
-                );
-                
-                spawn_events.send(SpawnEnemyEvent {
-                    position: spawn_pos,
-                    enemy_type: choose_enemy_type(wave_manager.current_wave),
+                BossPosition::Center => placement.level_center().unwrap_or(player_pos),
+            };
+            let position = placement.enemy_position(position, &mut rng).unwrap_or(position);
+            if roster.get(&boss.enemy).is_some() {
+                wave_manager.boss_spawned = true;
+                boss_events.write(SpawnBossEvent {
+                    position: position.extend(3.0),
+                    boss_id: boss.enemy,
+                    modifiers: boss.modifiers,
                 });
-                
-                wave_manager.enemies_spawned += 1;
-                println!("Spawned enemy {} of {}", wave_manager.enemies_spawned, calculate_wave_enemies(wave_manager.current_wave));
+            } else {
+                println!("Wave {} names unknown boss '{}'", wave_manager.current_wave, boss.enemy);
             }
+            wave_manager.boss_pending = false;
         }
     }
+    
+    // Complete once everything has spawned and died, or when the time limit runs out
+    let cleared = wave_manager.finished_spawning()
+        && wave_manager.enemies_alive == 0
+        && !wave_manager.boss_spawned;
+    let timed_out = wave_manager.plan.time_limit.is_some_and(|limit| wave_time >= limit);
+    if cleared || timed_out {
+        wave_manager.wave_complete = true;
+        wave_manager.intermission.reset();
+    }
 }
 
-
 fn spawn_collectibles(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
-    player_q: Query<&Transform, With<crate::game::player::Player>>,
+    placement: SpawnPlacement,
     collectible_q: Query<&Transform, With<crate::game::collectible::Collectible>>,
     _time: Res<Time>,
 ) {
     if collectible_q.iter().count() < 10 {
-        if let Ok(player_tf) = player_q.single() {
-            if rand::random::<f32>() < 0.02 {
-                let mut rng = rand::thread_rng();
-                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
-                let distance = rng.gen_range(100.0..300.0);
-                let spawn_pos = Vec3::new(
-                    player_tf.translation.x + angle.cos() * distance,
-                    player_tf.translation.y + angle.sin() * distance,
-                    2.0,
-                );
+        if rand::random::<f32>() < 0.02 {
+            let mut rng = rand::thread_rng();
+            // Fruit goes somewhere open and visible
+            if let Some(position) = placement.pickup_position(&mut rng) {
+                let spawn_pos = position.extend(2.0);
 
                 // Texture atlas has 8 frames (0..7)
                 let texture = asset_server.load("sprites/meyveler.png");
@@ -182,50 +265,22 @@ fn spawn_collectibles(
     }
 }
 
-fn update_difficulty(
+/// A boss wave only counts as complete once its boss is dead
+fn handle_boss_defeated(
+    mut game_events: EventReader<GameEvent>,
     mut wave_manager: ResMut<WaveManager>,
-    _time: Res<Time>,
 ) {
-    // Increase difficulty over time
-    wave_manager.difficulty_multiplier = 1.0 + (wave_manager.current_wave as f32 / 10.0) * 0.2;
-}
-
-fn calculate_wave_enemies(wave: u32) -> u32 {
-    5 + wave * 2 // Start smaller for testing
-}
-
-fn choose_enemy_type(wave: u32) -> EnemyType {
-    let mut rng = rand::thread_rng();
-    if wave < 3 {
-        EnemyType::Goblin
-    } else if wave < 6 {
-        match rng.gen_range(0..2) {
-            0 => EnemyType::Goblin,
-            _ => EnemyType::Skeleton,
-        }
-    } else if wave < 10 {
-        match rng.gen_range(0..3) {
-            0 => EnemyType::Goblin,
-            1 => EnemyType::Skeleton,
-            _ => EnemyType::Orc,
-        }
-    } else {
-        match rng.gen_range(0..5) {
-            0 => EnemyType::Goblin,
-            1 => EnemyType::Skeleton,
-            2 => EnemyType::Orc,
-            3 => EnemyType::DarkKnight,
-            _ => EnemyType::Necromancer,
+    for event in game_events.read() {
+        if let GameEventType::BossDefeated(_) = event.event_type {
+            wave_manager.boss_spawned = false;
         }
     }
 }
 
-fn choose_boss_type(wave: u32) -> EnemyType {
-    if wave < 10 {
-        EnemyType::GoblinKing
-    } else if wave < 20 {
-        EnemyType::LichLord
-    } else {
-        EnemyType::DragonKnight
-    }
+fn update_difficulty(
+    mut wave_manager: ResMut<WaveManager>,
+    _time: Res<Time>,
+) {
+    // Increase difficulty over time
+    wave_manager.difficulty_multiplier = 1.0 + (wave_manager.current_wave as f32 / 10.0) * 0.2;
 }
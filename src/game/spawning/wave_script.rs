@@ -0,0 +1,374 @@
+use bevy::prelude::*;
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use serde::Deserialize;
+use std::fmt;
+use crate::game::enemy::EnemyModifiers;
+use crate::game::enemy_definitions::EnemyRoster;
+
+pub const WAVE_SCRIPT: &str = "data/waves.json";
+
+/// Authored waves plus the rules for generating waves past the end of the script,
+/// loaded from `assets/data/waves.json`
+#[derive(Asset, TypePath, Clone, Deserialize)]
+pub struct WaveScript {
+    pub waves: Vec<WavePlan>,
+    pub endless: EndlessRules,
+}
+
+/// Everything that happens during one wave
+#[derive(Clone, Deserialize, Default)]
+pub struct WavePlan {
+    #[serde(default)]
+    pub groups: Vec<SpawnGroup>,
+    #[serde(default)]
+    pub events: Vec<TimedEvent>,
+    #[serde(default)]
+    pub boss: Option<BossPlacement>,
+    /// Seconds before the next wave starts even if enemies are left; None waits for a full clear
+    #[serde(default)]
+    pub time_limit: Option<f32>,
+}
+
+/// A batch of one enemy type spawned together `at` seconds into the wave
+#[derive(Clone, Deserialize)]
+pub struct SpawnGroup {
+    pub enemy: String,
+    pub count: u32,
+    #[serde(default)]
+    pub at: f32,
+    #[serde(default)]
+    pub pattern: SpawnPattern,
+    #[serde(default)]
+    pub modifiers: EnemyModifiers,
+}
+
+/// Where a group appears relative to the player
+#[derive(Clone, Copy, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SpawnPattern {
+    /// Evenly spaced around the player
+    Ring { radius: f32 },
+    /// A wall of enemies across a random side of the player
+    Line { distance: f32, spacing: f32 },
+    /// A tight cluster at one random point
+    Swarm { distance: f32, spread: f32 },
+    /// Just outside the edges of the screen
+    Ambush,
+    /// Random points at a random distance, one at a time
+    Scatter { min_distance: f32, max_distance: f32 },
+    /// The level's enemy spawn markers; scatters instead on maps without any
+    SpawnPoints,
+}
+
+impl SpawnPattern {
+    /// Ranges the pattern rolls from must be non-empty, or picking a position would panic
+    fn validate(&self) -> Result<(), String> {
+        match *self {
+            SpawnPattern::Swarm { spread, .. } if spread.is_nan() || spread < 0.0 => {
+                Err(format!("swarm spread must be zero or more, got {}", spread))
+            }
+            SpawnPattern::Scatter { min_distance, max_distance } if min_distance.is_nan() || max_distance.is_nan() || min_distance > max_distance => {
+                Err(format!("scatter min_distance {} is above max_distance {}", min_distance, max_distance))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for SpawnPattern {
+    fn default() -> Self {
+        SpawnPattern::Scatter { min_distance: 200.0, max_distance: 400.0 }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct TimedEvent {
+    pub at: f32,
+    #[serde(flatten)]
+    pub action: WaveAction,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum WaveAction {
+    Announce { text: String },
+    HealPlayer { amount: i32 },
+}
+
+#[derive(Clone, Deserialize)]
+pub struct BossPlacement {
+    pub enemy: String,
+    #[serde(default)]
+    pub at: f32,
+    #[serde(default)]
+    pub placement: BossPosition,
+    #[serde(default)]
+    pub modifiers: EnemyModifiers,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BossPosition {
+    /// Fixed offset from the player
+    Offset { x: f32, y: f32 },
+    /// Random direction, fixed distance from the player
+    Around { distance: f32 },
+    /// Center of the map
+    Center,
+}
+
+impl Default for BossPosition {
+    fn default() -> Self {
+        BossPosition::Offset { x: 300.0, y: 0.0 }
+    }
+}
+
+/// How waves keep scaling once the authored script runs out
+#[derive(Clone, Deserialize)]
+pub struct EndlessRules {
+    pub base_count: u32,
+    pub count_per_wave: u32,
+    pub group_size: u32,
+    pub group_interval: f32,
+    pub boss_every: u32,
+    pub patterns: Vec<SpawnPattern>,
+    pub health_per_wave: f32,  // Added to the health multiplier per endless wave
+    pub damage_per_wave: f32,
+    pub elite_chance: f32,
+    pub elite_chance_per_wave: f32,
+    pub max_elite_chance: f32,
+    #[serde(default)]
+    pub champion_chance: f32,  // Chance an elite is promoted again to a champion
+    #[serde(default)]
+    pub time_limit: Option<f32>,
+}
+
+#[derive(Debug)]
+pub enum WaveScriptError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Invalid { wave: usize, reason: String },
+}
+
+impl fmt::Display for WaveScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaveScriptError::Io(e) => write!(f, "could not read wave script: {}", e),
+            WaveScriptError::Json(e) => write!(f, "invalid wave script json: {}", e),
+            WaveScriptError::Invalid { wave, reason } => write!(f, "wave {}: {}", wave, reason),
+        }
+    }
+}
+
+impl std::error::Error for WaveScriptError {}
+
+impl From<std::io::Error> for WaveScriptError {
+    fn from(e: std::io::Error) -> Self {
+        WaveScriptError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for WaveScriptError {
+    fn from(e: serde_json::Error) -> Self {
+        WaveScriptError::Json(e)
+    }
+}
+
+impl WaveScript {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WaveScriptError> {
+        let script: WaveScript = serde_json::from_slice(bytes)?;
+
+        for (index, wave) in script.waves.iter().enumerate() {
+            let invalid = |reason: String| Err(WaveScriptError::Invalid { wave: index + 1, reason });
+            if wave.groups.is_empty() && wave.boss.is_none() {
+                return invalid("needs at least one spawn group or a boss".to_string());
+            }
+            if wave.groups.iter().any(|group| group.count == 0) {
+                return invalid("spawn groups need a count above zero".to_string());
+            }
+            if let Err(reason) = wave.groups.iter().try_for_each(|group| group.pattern.validate()) {
+                return invalid(reason);
+            }
+        }
+        if script.endless.patterns.is_empty() {
+            return Err(WaveScriptError::Invalid {
+                wave: script.waves.len() + 1,
+                reason: "endless rules need at least one spawn pattern".to_string(),
+            });
+        }
+        if let Err(reason) = script.endless.patterns.iter().try_for_each(SpawnPattern::validate) {
+            return Err(WaveScriptError::Invalid { wave: script.waves.len() + 1, reason });
+        }
+        Ok(script)
+    }
+
+    /// The plan for a 1-based wave number: authored if the script has it, generated otherwise
+    pub fn plan(&self, wave: u32, roster: &EnemyRoster) -> WavePlan {
+        match self.waves.get(wave.saturating_sub(1) as usize) {
+            Some(plan) => plan.clone(),
+            None => self.endless.generate(wave, wave - self.waves.len() as u32, roster),
+        }
+    }
+}
+
+impl EndlessRules {
+    /// Extrapolates a wave from the roster's spawn rules. `depth` counts waves past the script.
+    /// Seeded by the wave number, so the same wave always plays out the same way.
+    fn generate(&self, wave: u32, depth: u32, roster: &EnemyRoster) -> WavePlan {
+        let mut rng = StdRng::seed_from_u64(wave as u64);
+        let total = self.base_count + self.count_per_wave * depth;
+        let elite_chance = (self.elite_chance + self.elite_chance_per_wave * depth as f32).min(self.max_elite_chance);
+        let scaled = EnemyModifiers {
+            health: 1.0 + self.health_per_wave * depth as f32,
+            damage: 1.0 + self.damage_per_wave * depth as f32,
+            ..default()
+        };
+
+        let mut groups = Vec::new();
+        let mut remaining = total;
+        while remaining > 0 {
+            let count = remaining.min(self.group_size.max(1));
+            remaining -= count;
+            let Some(enemy) = choose_enemy(roster, wave, &mut rng) else { break };
+            let elite = rng.gen::<f32>() < elite_chance;
+            groups.push(SpawnGroup {
+                enemy,
+                count,
+                at: groups.len() as f32 * self.group_interval,
+                pattern: self.patterns[rng.gen_range(0..self.patterns.len())],
+                modifiers: EnemyModifiers {
+                    elite,
+                    champion: elite && rng.gen::<f32>() < self.champion_chance,
+                    ..scaled
+                },
+            });
+        }
+
+        let boss = (self.boss_every > 0 && wave % self.boss_every == 0)
+            .then(|| roster.boss_for_wave(wave))
+            .flatten()
+            .map(|boss| BossPlacement {
+                enemy: boss.id.clone(),
+                at: 0.0,
+                placement: BossPosition::Around { distance: 300.0 },
+                modifiers: scaled,
+            });
+
+        WavePlan {
+            groups,
+            events: vec![TimedEvent {
+                at: 0.0,
+                action: WaveAction::Announce { text: format!("Endless wave {}", wave) },
+            }],
+            boss,
+            time_limit: self.time_limit,
+        }
+    }
+}
+
+/// Weighted pick among the definitions whose spawn rules allow this wave
+pub fn choose_enemy(roster: &EnemyRoster, wave: u32, rng: &mut impl Rng) -> Option<String> {
+    let total: f32 = roster.wave_candidates(wave).map(|definition| definition.spawn.weight).sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let mut roll = rng.gen_range(0.0..total);
+    for definition in roster.wave_candidates(wave) {
+        if roll < definition.spawn.weight {
+            return Some(definition.id.clone());
+        }
+        roll -= definition.spawn.weight;
+    }
+    roster.wave_candidates(wave).last().map(|definition| definition.id.clone())
+}
+
+/// Desired world positions for a group of `count` enemies, before placement checks them against the map.
+/// `view_half` is half the visible area, for ambushes; `spawn_points` are the level's markers.
+pub fn pattern_positions(
+    pattern: SpawnPattern,
+    center: Vec2,
+    count: u32,
+    view_half: Vec2,
+    spawn_points: &[Vec2],
+    rng: &mut impl Rng,
+) -> Vec<Vec2> {
+    match pattern {
+        SpawnPattern::Ring { radius } => {
+            let offset = rng.gen_range(0.0..std::f32::consts::TAU);
+            (0..count)
+                .map(|i| center + Vec2::from_angle(offset + i as f32 / count as f32 * std::f32::consts::TAU) * radius)
+                .collect()
+        }
+        SpawnPattern::Line { distance, spacing } => {
+            let direction = random_direction(rng);
+            let start = -(count as f32 - 1.0) * spacing / 2.0;
+            (0..count)
+                .map(|i| center + direction * distance + direction.perp() * (start + i as f32 * spacing))
+                .collect()
+        }
+        SpawnPattern::Swarm { distance, spread } => {
+            let anchor = center + random_direction(rng) * distance;
+            (0..count)
+                .map(|_| anchor + Vec2::new(rng.gen_range(-spread..=spread), rng.gen_range(-spread..=spread)))
+                .collect()
+        }
+        SpawnPattern::Ambush => {
+            // A random point on the rectangle just outside the screen
+            let half = view_half + Vec2::splat(32.0);
+            (0..count)
+                .map(|_| {
+                    let edge = if rng.gen_bool(0.5) {
+                        Vec2::new(if rng.gen_bool(0.5) { half.x } else { -half.x }, rng.gen_range(-half.y..=half.y))
+                    } else {
+                        Vec2::new(rng.gen_range(-half.x..=half.x), if rng.gen_bool(0.5) { half.y } else { -half.y })
+                    };
+                    center + edge
+                })
+                .collect()
+        }
+        SpawnPattern::Scatter { min_distance, max_distance } => {
+            (0..count)
+                .map(|_| center + random_direction(rng) * rng.gen_range(min_distance..=max_distance))
+                .collect()
+        }
+        SpawnPattern::SpawnPoints if spawn_points.is_empty() => {
+            pattern_positions(SpawnPattern::default(), center, count, view_half, spawn_points, rng)
+        }
+        SpawnPattern::SpawnPoints => {
+            (0..count)
+                .map(|_| spawn_points[rng.gen_range(0..spawn_points.len())])
+                .collect()
+        }
+    }
+}
+
+fn random_direction(rng: &mut impl Rng) -> Vec2 {
+    Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU))
+}
+
+#[derive(Default)]
+pub struct WaveScriptLoader;
+
+impl AssetLoader for WaveScriptLoader {
+    type Asset = WaveScript;
+    type Settings = ();
+    type Error = WaveScriptError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        WaveScript::from_bytes(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
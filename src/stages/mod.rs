@@ -2,6 +2,8 @@ pub mod stage_manager;
 pub mod stage_transition;
 
 use bevy::prelude::*;
+use crate::core::state::PlayState;
+use crate::world::level_loader;
 
 pub struct StagesPlugin;
 
@@ -9,6 +11,15 @@ impl Plugin for StagesPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<stage_manager::StageManager>()
-            .add_systems(Update, stage_transition::handle_stage_transitions);
+            .add_event::<stage_transition::StageCompleteEvent>()
+            .add_systems(Update, (
+                stage_transition::begin_stage_transition,
+                stage_transition::handle_stage_transitions.run_if(in_state(PlayState::Transitioning)),
+            ).chain())
+            .add_systems(OnEnter(PlayState::Transitioning), (
+                level_loader::cleanup_level,
+                stage_transition::teardown_stage_entities,
+                stage_transition::load_next_stage,
+            ).chain());
     }
 }
@@ -1,9 +1,11 @@
 use bevy::prelude::*;
+use crate::world::level_loader::LevelSource;
 
 #[derive(Resource)]
 pub struct StageManager {
     pub current_stage: usize,
     pub stages_completed: Vec<usize>,
+    pub stage_levels: Vec<LevelSource>, // Map for each stage, in play order
 }
 
 impl Default for StageManager {
@@ -11,6 +13,39 @@ impl Default for StageManager {
         Self {
             current_stage: 1,
             stages_completed: Vec::new(),
+            stage_levels: vec![
+                LevelSource::File("levels/level1.json".to_string()),
+                LevelSource::File("levels/level2.json".to_string()),
+                LevelSource::Generated(rand::random()),
+                LevelSource::Endless(rand::random()),
+            ],
         }
     }
 }
+
+impl StageManager {
+    /// Map source for the current stage
+    pub fn current_level_source(&self) -> Option<&LevelSource> {
+        self.stage_levels.get(self.current_stage.saturating_sub(1))
+    }
+
+    pub fn has_next_stage(&self) -> bool {
+        self.current_stage < self.stage_levels.len()
+    }
+
+    /// Marks the current stage as completed
+    pub fn complete_current_stage(&mut self) {
+        if !self.stages_completed.contains(&self.current_stage) {
+            self.stages_completed.push(self.current_stage);
+        }
+    }
+
+    /// Moves on to the next stage, returning its map source
+    pub fn advance(&mut self) -> Option<&LevelSource> {
+        if !self.has_next_stage() {
+            return None;
+        }
+        self.current_stage += 1;
+        self.current_level_source()
+    }
+}
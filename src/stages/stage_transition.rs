@@ -1,5 +1,129 @@
-// use bevy::prelude::*;
+use bevy::prelude::*;
+use crate::core::state::{GameState, PlayState};
+use crate::core::save_system::SaveData;
+use crate::game::player::Player;
+use crate::game::enemy::Enemy;
+use crate::game::enemy_archetypes::{EnemyProjectile, Corpse};
+use crate::game::boss::HazardZone;
+use crate::game::collectible::Collectible;
+use crate::game::abilities::projectile_system::Projectile;
+use crate::game::abilities::area_effects::AreaEffect;
+use crate::game::abilities::summons::Summon;
+use crate::game::combat::damage_numbers::{DamageNumber, DamageNumberPool};
+use crate::ui::health_bars::HealthBar;
+use crate::world::level_asset::LevelAsset;
+use crate::world::level_loader::CurrentLevel;
+use crate::world::chunks::ChunkManager;
+use super::stage_manager::StageManager;
 
-pub fn handle_stage_transitions() {
-    // Stage transition logic
+/// Sent when the current stage's goal is met (the player used a portal, or another goal was cleared)
+#[derive(Event)]
+pub struct StageCompleteEvent {
+    pub stage: usize,
+}
+
+/// Records the completed stage and starts the transition to the next one
+pub fn begin_stage_transition(
+    mut stage_events: EventReader<StageCompleteEvent>,
+    mut stage_manager: ResMut<StageManager>,
+    mut save_data: ResMut<SaveData>,
+    play_state: Res<State<PlayState>>,
+    mut next_play_state: ResMut<NextState<PlayState>>,
+) {
+    // Several goals can fire in the same frame; they all start the same transition
+    let Some(event) = stage_events.read().last() else { return };
+    if *play_state.get() == PlayState::Transitioning || event.stage != stage_manager.current_stage {
+        return;
+    }
+
+    stage_manager.complete_current_stage();
+    if !save_data.completed_levels.contains(&event.stage) {
+        save_data.completed_levels.push(event.stage);
+    }
+
+    println!("Stage {} complete!", event.stage);
+    next_play_state.set(PlayState::Transitioning);
+}
+
+/// Removes everything that belongs to the old stage. The player entity is kept,
+/// so its PowerUpSlots, abilities and currency carry over to the next stage.
+pub fn teardown_stage_entities(
+    mut commands: Commands,
+    enemy_q: Query<Entity, With<Enemy>>,
+    collectible_q: Query<Entity, With<Collectible>>,
+    projectile_q: Query<Entity, With<Projectile>>,
+    area_q: Query<Entity, With<AreaEffect>>,
+    enemy_projectile_q: Query<Entity, With<EnemyProjectile>>,
+    corpse_q: Query<Entity, With<Corpse>>,
+    hazard_q: Query<Entity, With<HazardZone>>,
+    summon_q: Query<Entity, With<Summon>>,
+    health_bar_q: Query<(Entity, &HealthBar)>,
+    player_q: Query<Entity, With<Player>>,
+    mut number_q: Query<(Entity, &mut DamageNumber, &mut Visibility)>,
+    mut number_pool: ResMut<DamageNumberPool>,
+) {
+    for entity in enemy_q.iter()
+        .chain(collectible_q.iter())
+        .chain(projectile_q.iter())
+        .chain(area_q.iter())
+        .chain(enemy_projectile_q.iter())
+        .chain(corpse_q.iter())
+        .chain(hazard_q.iter())
+        .chain(summon_q.iter())
+    {
+        commands.entity(entity).despawn();
+    }
+
+    // Enemy health bars are separate entities that would otherwise be left floating
+    for (entity, bar) in health_bar_q.iter() {
+        if !player_q.contains(bar.owner) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    // Damage numbers still showing go back to the pool instead of hanging over the new map
+    for (entity, mut number, mut visibility) in number_q.iter_mut() {
+        if number.active {
+            number.active = false;
+            *visibility = Visibility::Hidden;
+            number_pool.free.push(entity);
+        }
+    }
+}
+
+/// Points the level loader at the next stage's map, or ends the run after the last stage
+pub fn load_next_stage(
+    mut stage_manager: ResMut<StageManager>,
+    current_level: Option<ResMut<CurrentLevel>>,
+    asset_server: Res<AssetServer>,
+    mut levels: ResMut<Assets<LevelAsset>>,
+    mut chunk_manager: ResMut<ChunkManager>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_play_state: ResMut<NextState<PlayState>>,
+) {
+    let Some(mut current_level) = current_level else { return };
+
+    match stage_manager.advance() {
+        Some(source) => {
+            *current_level = CurrentLevel::start(source, &asset_server, &mut levels, &mut chunk_manager);
+            println!("Loading stage {}", stage_manager.current_stage);
+        }
+        None => {
+            println!("All stages complete!");
+            next_play_state.set(PlayState::Exploring);
+            next_game_state.set(GameState::Victory);
+        }
+    }
+}
+
+/// Returns to normal play once the next stage has been spawned
+pub fn handle_stage_transitions(
+    current_level: Option<Res<CurrentLevel>>,
+    mut next_play_state: ResMut<NextState<PlayState>>,
+) {
+    let Some(current_level) = current_level else { return };
+
+    if current_level.spawned {
+        next_play_state.set(PlayState::Exploring);
+    }
 }
@@ -12,6 +12,7 @@ impl Plugin for AchievementPlugin {
             .add_systems(Startup, initialize_achievements)
             .add_systems(Update, (
                 track_achievement_progress,
+                track_boss_kills,
                 check_achievement_completion,
                 handle_achievement_rewards,
             ));
@@ -49,6 +50,7 @@ pub enum AchievementCategory {
 #[derive(Clone)]
 pub enum AchievementRequirement {
     KillEnemies(u32),
+    KillElites(u32), // Elites and champions only
     CollectCoins(u32),
     ReachWave(u32),
     DefeatBoss(String),
@@ -169,6 +171,23 @@ fn initialize_achievements(mut registry: ResMut<AchievementRegistry>) {
             hidden: false,
             tier: AchievementTier::Gold,
         },
+        Achievement {
+            id: "elite_hunter".to_string(),
+            name: "Elite Hunter".to_string(),
+            description: "Defeat 25 elite or champion enemies".to_string(),
+            icon: "icons/skull.png".to_string(),
+            category: AchievementCategory::Combat,
+            requirement: AchievementRequirement::KillElites(25),
+            reward: AchievementReward {
+                currency: Some((CurrencyType::Gems, 15)),
+                unlock: None,
+                title: Some("Elite Hunter".to_string()),
+                cosmetic: None,
+                bonus_stats: Some(vec![(StatType::Damage, 5.0)]),
+            },
+            hidden: false,
+            tier: AchievementTier::Silver,
+        },
         Achievement {
             id: "flawless_victory".to_string(),
             name: "Flawless Victory".to_string(),
@@ -203,6 +222,57 @@ fn initialize_achievements(mut registry: ResMut<AchievementRegistry>) {
             hidden: false,
             tier: AchievementTier::Gold,
         },
+        Achievement {
+            id: "king_slayer".to_string(),
+            name: "Regicide".to_string(),
+            description: "Defeat the Goblin King".to_string(),
+            icon: "icons/crown.png".to_string(),
+            category: AchievementCategory::Combat,
+            requirement: AchievementRequirement::DefeatBoss("goblin_king".to_string()),
+            reward: AchievementReward {
+                currency: Some((CurrencyType::Gems, 10)),
+                unlock: None,
+                title: Some("Regicide".to_string()),
+                cosmetic: None,
+                bonus_stats: None,
+            },
+            hidden: false,
+            tier: AchievementTier::Silver,
+        },
+        Achievement {
+            id: "lich_bane".to_string(),
+            name: "Lich Bane".to_string(),
+            description: "Defeat the Lich Lord".to_string(),
+            icon: "icons/skull.png".to_string(),
+            category: AchievementCategory::Combat,
+            requirement: AchievementRequirement::DefeatBoss("lich_lord".to_string()),
+            reward: AchievementReward {
+                currency: Some((CurrencyType::SoulShards, 1)),
+                unlock: None,
+                title: Some("Lich Bane".to_string()),
+                cosmetic: None,
+                bonus_stats: None,
+            },
+            hidden: false,
+            tier: AchievementTier::Gold,
+        },
+        Achievement {
+            id: "dragon_slayer".to_string(),
+            name: "Dragon Slayer".to_string(),
+            description: "Defeat the Dragon Knight".to_string(),
+            icon: "icons/dragon.png".to_string(),
+            category: AchievementCategory::Combat,
+            requirement: AchievementRequirement::DefeatBoss("dragon_knight".to_string()),
+            reward: AchievementReward {
+                currency: Some((CurrencyType::Gems, 50)),
+                unlock: None,
+                title: Some("Dragon Slayer".to_string()),
+                cosmetic: Some("dragon_slayer_badge".to_string()),
+                bonus_stats: Some(vec![(StatType::Damage, 10.0)]),
+            },
+            hidden: false,
+            tier: AchievementTier::Platinum,
+        },
     ];
     
     for achievement in achievements {
@@ -226,6 +296,9 @@ fn track_achievement_progress(
             AchievementRequirement::KillEnemies(required) => {
                 game_stats.enemies_killed.min(*required)
             }
+            AchievementRequirement::KillElites(required) => {
+                game_stats.elites_killed.min(*required)
+            }
             AchievementRequirement::CollectCoins(required) => {
                 game_stats.coins_collected.min(*required)
             }
@@ -242,6 +315,24 @@ fn track_achievement_progress(
     }
 }
 
+fn track_boss_kills(
+    mut game_events: EventReader<crate::core::events::GameEvent>,
+    mut player_achievements: ResMut<PlayerAchievements>,
+    registry: Res<AchievementRegistry>,
+) {
+    for event in game_events.read() {
+        let crate::core::events::GameEventType::BossDefeated(boss_type) = event.event_type else { continue };
+        
+        for (id, achievement) in registry.achievements.iter() {
+            if let AchievementRequirement::DefeatBoss(boss_id) = &achievement.requirement {
+                if boss_id == boss_type.id() {
+                    player_achievements.progress.insert(id.clone(), 1);
+                }
+            }
+        }
+    }
+}
+
 fn check_achievement_completion(
     mut player_achievements: ResMut<PlayerAchievements>,
     registry: Res<AchievementRegistry>,
@@ -258,9 +349,11 @@ fn check_achievement_completion(
         let progress = player_achievements.progress.get(id).copied().unwrap_or(0);
         let completed = match &achievement.requirement {
             AchievementRequirement::KillEnemies(required) => progress >= *required,
+            AchievementRequirement::KillElites(required) => progress >= *required,
             AchievementRequirement::CollectCoins(required) => progress >= *required,
             AchievementRequirement::ReachWave(required) => progress >= *required,
             AchievementRequirement::ReachCombo(required) => progress >= *required,
+            AchievementRequirement::DefeatBoss(_) => progress >= 1,
             AchievementRequirement::Custom(_, required, _) => progress >= *required,
             _ => false,
         };
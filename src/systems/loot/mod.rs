@@ -165,6 +165,7 @@ pub struct DropLootEvent {
     pub position: Vec3,
     pub source: LootSource,
     pub luck_bonus: f32,
+    pub rolls: u32, // Times the table is rolled
 }
 
 #[derive(Clone)]
@@ -239,6 +240,127 @@ fn initialize_loot_tables(mut loot_table: ResMut<LootTable>) {
     ];
     
     loot_table.boss_drops.insert("goblin_king".to_string(), goblin_king_drops);
+    
+    let lich_lord_drops = vec![
+        LootEntry {
+            loot: LootType::Currency(CurrencyType::SoulShards, 1),
+            weight: 10.0,
+            min_wave: 0,
+            guaranteed: true,
+        },
+        LootEntry {
+            loot: LootType::Material(MaterialType::SoulEssence),
+            weight: 10.0,
+            min_wave: 0,
+            guaranteed: true,
+        },
+        LootEntry {
+            loot: LootType::Equipment(Equipment {
+                id: "lich_phylactery".to_string(),
+                name: "Lich's Phylactery".to_string(),
+                slot: EquipmentSlot::Accessory,
+                stats: HashMap::from([
+                    (StatType::Health, 30.0),
+                    (StatType::LifeSteal, 0.05),
+                ]),
+                special_effects: vec![SpecialEffect::OnKill("raise_skeleton".to_string())],
+                set_bonus: None,
+            }),
+            weight: 4.0,
+            min_wave: 10,
+            guaranteed: false,
+        },
+    ];
+    
+    loot_table.boss_drops.insert("lich_lord".to_string(), lich_lord_drops);
+    
+    let dragon_knight_drops = vec![
+        LootEntry {
+            loot: LootType::Currency(CurrencyType::Gems, 15),
+            weight: 10.0,
+            min_wave: 0,
+            guaranteed: true,
+        },
+        LootEntry {
+            loot: LootType::Material(MaterialType::DragonScale),
+            weight: 10.0,
+            min_wave: 0,
+            guaranteed: true,
+        },
+        LootEntry {
+            loot: LootType::Equipment(Equipment {
+                id: "dragon_lance".to_string(),
+                name: "Dragon Knight's Lance".to_string(),
+                slot: EquipmentSlot::Weapon,
+                stats: HashMap::from([
+                    (StatType::Damage, 25.0),
+                    (StatType::CritChance, 0.1),
+                ]),
+                special_effects: vec![SpecialEffect::OnHit("burn".to_string(), 0.2)],
+                set_bonus: None,
+            }),
+            weight: 3.0,
+            min_wave: 15,
+            guaranteed: false,
+        },
+    ];
+    
+    loot_table.boss_drops.insert("dragon_knight".to_string(), dragon_knight_drops);
+    
+    // Chest drops; level files pick a table per chest, "chest" is the default
+    let chest_drops = vec![
+        LootEntry {
+            loot: LootType::Currency(CurrencyType::Coins, 25),
+            weight: 10.0,
+            min_wave: 0,
+            guaranteed: true,
+        },
+        LootEntry {
+            loot: LootType::Consumable(ConsumableItem::HealthPotion(25)),
+            weight: 4.0,
+            min_wave: 0,
+            guaranteed: false,
+        },
+        LootEntry {
+            loot: LootType::Material(MaterialType::MagicDust),
+            weight: 2.0,
+            min_wave: 0,
+            guaranteed: false,
+        },
+    ];
+    
+    let locked_chest_drops = vec![
+        LootEntry {
+            loot: LootType::Currency(CurrencyType::Gems, 3),
+            weight: 10.0,
+            min_wave: 0,
+            guaranteed: true,
+        },
+        LootEntry {
+            loot: LootType::Equipment(Equipment {
+                id: "ember_blade".to_string(),
+                name: "Ember Blade".to_string(),
+                slot: EquipmentSlot::Weapon,
+                stats: HashMap::from([
+                    (StatType::Damage, 8.0),
+                ]),
+                special_effects: vec![SpecialEffect::OnHit("burn".to_string(), 0.15)],
+                set_bonus: None,
+            }),
+            weight: 4.0,
+            min_wave: 0,
+            guaranteed: false,
+        },
+        LootEntry {
+            loot: LootType::Consumable(ConsumableItem::Shield(50, 10.0)),
+            weight: 3.0,
+            min_wave: 0,
+            guaranteed: false,
+        },
+    ];
+    
+    loot_table.chest_drops.insert("chest".to_string(), chest_drops);
+    loot_table.chest_drops.insert("locked_chest".to_string(), locked_chest_drops);
 }
 
 fn handle_loot_drops(
@@ -258,10 +380,12 @@ fn handle_loot_drops(
         if let Some(drop_list) = drops {
             let mut rng = rand::thread_rng();
             
-            for entry in drop_list {
-                if entry.guaranteed || rng.gen::<f32>() < calculate_drop_chance(entry.weight, event.luck_bonus) {
-                    let rarity = determine_rarity(event.luck_bonus);
-                    spawn_loot_drop(&mut commands, event.position, entry.loot.clone(), rarity, &asset_server);
+            for _ in 0..event.rolls.max(1) {
+                for entry in drop_list {
+                    if entry.guaranteed || rng.gen::<f32>() < calculate_drop_chance(entry.weight, event.luck_bonus) {
+                        let rarity = determine_rarity(event.luck_bonus);
+                        spawn_loot_drop(&mut commands, event.position, entry.loot.clone(), rarity, &asset_server);
+                    }
                 }
             }
         }
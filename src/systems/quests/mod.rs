@@ -50,6 +50,7 @@ pub enum QuestType {
 #[derive(Clone, Debug)]
 pub enum QuestObjective {
     KillEnemies(String, u32), // enemy type, count
+    KillElites(u32), // elites and champions, count
     CollectItems(String, u32), // item type, count
     ReachWave(u32),
     SurviveTime(f32),
@@ -126,6 +127,21 @@ fn initialize_quests(mut quest_manager: ResMut<QuestManager>) {
             time_limit: Some(86400.0), // 24 hours
             repeatable: true,
         },
+        Quest {
+            id: "daily_elite_hunter".to_string(),
+            name: "Big Game".to_string(),
+            description: "Defeat 5 elite enemies".to_string(),
+            quest_type: QuestType::Daily,
+            objectives: vec![QuestObjective::KillElites(5)],
+            rewards: QuestRewards {
+                experience: 150,
+                currency: vec![(CurrencyType::Gems, 5)],
+                items: vec![],
+                unlock: None,
+            },
+            time_limit: Some(86400.0),
+            repeatable: true,
+        },
         Quest {
             id: "daily_collector".to_string(),
             name: "Treasure Hunter".to_string(),
@@ -206,6 +222,9 @@ fn update_quest_progress(
                 QuestObjective::KillEnemies(_, target) => {
                     game_stats.enemies_killed.min(*target)
                 }
+                QuestObjective::KillElites(target) => {
+                    game_stats.elites_killed.min(*target)
+                }
                 QuestObjective::ReachWave(target) => {
                     (wave_manager.current_wave).min(*target)
                 }
@@ -237,6 +256,9 @@ fn update_quest_progress(
                 QuestObjective::KillEnemies(_, target) => {
                     game_stats.enemies_killed.min(*target)
                 }
+                QuestObjective::KillElites(target) => {
+                    game_stats.elites_killed.min(*target)
+                }
                 QuestObjective::ReachWave(target) => {
                     (wave_manager.current_wave).min(*target)
                 }
@@ -268,6 +290,9 @@ fn update_quest_progress(
                 QuestObjective::KillEnemies(_, target) => {
                     game_stats.enemies_killed.min(*target)
                 }
+                QuestObjective::KillElites(target) => {
+                    game_stats.elites_killed.min(*target)
+                }
                 QuestObjective::ReachWave(target) => {
                     (wave_manager.current_wave).min(*target)
                 }
@@ -308,6 +333,7 @@ fn check_quest_completion(
             let progress = active_quest.progress.get(&idx).copied().unwrap_or(0);
             match obj {
                 QuestObjective::KillEnemies(_, target) => progress >= *target,
+                QuestObjective::KillElites(target) => progress >= *target,
                 QuestObjective::CollectItems(_, target) => progress >= *target,
                 QuestObjective::ReachWave(target) => progress >= *target,
                 QuestObjective::ReachCombo(target) => progress >= *target,
@@ -335,6 +361,7 @@ fn check_quest_completion(
             let progress = active_quest.progress.get(&idx).copied().unwrap_or(0);
             match obj {
                 QuestObjective::KillEnemies(_, target) => progress >= *target,
+                QuestObjective::KillElites(target) => progress >= *target,
                 QuestObjective::CollectItems(_, target) => progress >= *target,
                 QuestObjective::ReachWave(target) => progress >= *target,
                 QuestObjective::ReachCombo(target) => progress >= *target,
@@ -362,6 +389,7 @@ fn check_quest_completion(
             let progress = active_quest.progress.get(&idx).copied().unwrap_or(0);
             match obj {
                 QuestObjective::KillEnemies(_, target) => progress >= *target,
+                QuestObjective::KillElites(target) => progress >= *target,
                 QuestObjective::CollectItems(_, target) => progress >= *target,
                 QuestObjective::ReachWave(target) => progress >= *target,
                 QuestObjective::ReachCombo(target) => progress >= *target,
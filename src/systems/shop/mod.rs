@@ -247,9 +247,32 @@ fn handle_purchases(
 fn apply_upgrade_effects(
     purchased: Res<PurchasedUpgrades>,
     shop: Res<ShopInventory>,
-    mut player_q: Query<&mut crate::combat::CombatStats, With<crate::player::Player>>,
+    mut modifiers: ResMut<crate::game::combat::damage::DamageModifiers>,
 ) {
-    // Apply all purchased upgrades to player stats
+    if !purchased.is_changed() {
+        return;
+    }
+    
+    // Rebuild the shop share of the damage bonuses from every purchased stack
+    let mut bonuses = crate::game::combat::damage::DamageBonuses::default();
+    for (item_id, count) in &purchased.upgrades {
+        let Some(item) = shop.items.iter().find(|item| &item.id == item_id) else { continue };
+        let stacks = *count as f32;
+        
+        match &item.upgrade_type {
+            UpgradeType::StatBoost(StatType::Damage, value) => bonuses.flat_damage += (value * stacks) as i32,
+            UpgradeType::PercentBoost(StatType::Damage, value) => bonuses.damage_percent += value * stacks,
+            UpgradeType::StatBoost(StatType::CritChance, value)
+            | UpgradeType::PercentBoost(StatType::CritChance, value) => bonuses.crit_chance += value * stacks,
+            UpgradeType::StatBoost(StatType::CritDamage, value)
+            | UpgradeType::PercentBoost(StatType::CritDamage, value) => bonuses.crit_damage += value * stacks,
+            UpgradeType::StatBoost(StatType::Armor, value) => bonuses.armor += (value * stacks) as i32,
+            UpgradeType::PassiveEffect(PassiveType::Thorns(fraction)) => bonuses.thorns += fraction * stacks,
+            UpgradeType::PassiveEffect(PassiveType::DodgeChance(chance)) => bonuses.dodge_chance += chance * stacks,
+            _ => {}
+        }
+    }
+    modifiers.upgrades = bonuses;
 }
 
 fn refresh_shop_on_wave_clear(
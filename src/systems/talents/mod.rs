@@ -249,6 +249,22 @@ fn initialize_talent_tree(mut talent_tree: ResMut<TalentTree>) {
         effects: vec![TalentEffect::PercentIncrease(StatType::Speed, 0.05)],
     });
     
+    utility_tree.talents.insert("quick_recovery".to_string(), Talent {
+        id: "quick_recovery".to_string(),
+        name: "Quick Recovery".to_string(),
+        description: "-4% ability cooldowns per rank".to_string(),
+        icon: "icons/hourglass.png".to_string(),
+        max_ranks: 5,
+        cost_per_rank: 1,
+        position: Vec2::new(0.0, -50.0),
+        requirements: TalentRequirements {
+            level: 3,
+            prerequisite_talents: vec!["swift_feet".to_string()],
+            points_in_tree: 2,
+        },
+        effects: vec![TalentEffect::StatIncrease(StatType::CooldownReduction, 0.04)],
+    });
+    
     talent_tree.trees.insert(TalentTreeType::Utility, utility_tree);
 }
 
@@ -280,9 +296,43 @@ fn handle_talent_unlocks(
 fn apply_talent_effects(
     player_talents: Res<PlayerTalents>,
     talent_tree: Res<TalentTree>,
-    mut player_q: Query<&mut crate::combat::CombatStats, With<crate::player::Player>>,
+    mut modifiers: ResMut<crate::game::combat::damage::DamageModifiers>,
+    mut ability_modifiers: ResMut<crate::game::abilities::AbilityModifiers>,
 ) {
-    // Apply all unlocked talent effects to player
+    if !player_talents.is_changed() {
+        return;
+    }
+    
+    // Rebuild the talent share of the damage bonuses and the ability changes from every unlocked rank
+    let mut bonuses = crate::game::combat::damage::DamageBonuses::default();
+    let mut abilities = crate::game::abilities::AbilityModifiers::default();
+    for (talent_id, rank) in &player_talents.unlocked_talents {
+        let Some(talent) = talent_tree.trees.values().find_map(|tree| tree.talents.get(talent_id)) else { continue };
+        let ranks = *rank as f32;
+        
+        for effect in &talent.effects {
+            match effect {
+                TalentEffect::PercentIncrease(StatType::Damage, value) => bonuses.damage_percent += value * ranks,
+                TalentEffect::StatIncrease(StatType::Damage, value) => bonuses.flat_damage += (value * ranks) as i32,
+                TalentEffect::PercentIncrease(StatType::CritChance, value)
+                | TalentEffect::StatIncrease(StatType::CritChance, value) => bonuses.crit_chance += value * ranks,
+                TalentEffect::PercentIncrease(StatType::CritDamage, value)
+                | TalentEffect::StatIncrease(StatType::CritDamage, value) => bonuses.crit_damage += value * ranks,
+                TalentEffect::StatIncrease(StatType::Armor, value) => bonuses.armor += (value * ranks) as i32,
+                TalentEffect::PercentIncrease(StatType::CooldownReduction, value)
+                | TalentEffect::StatIncrease(StatType::CooldownReduction, value) => abilities.cooldown_reduction += value * ranks,
+                TalentEffect::ModifyAbility(target, modification) => {
+                    for _ in 0..*rank {
+                        abilities.modifications.push((target.clone(), modification.clone()));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    modifiers.talents = bonuses;
+    ability_modifiers.cooldown_reduction = abilities.cooldown_reduction;
+    ability_modifiers.modifications = abilities.modifications;
 }
 
 fn calculate_talent_points(
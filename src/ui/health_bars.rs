@@ -1,5 +1,7 @@
 use bevy::prelude::*;
+use std::collections::HashSet;
 use crate::game::combat::Health; // <- adjust if Health lives elsewhere
+use crate::game::combat::effects::{StatusEffects, StatusKind};
 
 // ---- local settings & constants (self-contained) ----
 #[derive(Resource)]
@@ -16,6 +18,8 @@ impl Default for HealthBarSettings {
 const HEALTH_BAR_WIDTH: f32 = 24.0;
 const HEALTH_BAR_HEIGHT: f32 = 3.0;
 const HEALTH_BAR_OFFSET: f32 = 18.0;
+const STATUS_ICON_SIZE: f32 = 4.0;
+const STATUS_ICON_OFFSET: f32 = 23.0; // Row just above the health bar
 
 // Marker for both foreground (colored) and background bars
 #[derive(Component)]
@@ -24,13 +28,20 @@ pub struct HealthBar {
     pub foreground: bool, // true = colored (health), false = background (grey)
 }
 
+// Colored pip above the health bar, one per active status
+#[derive(Component)]
+pub struct StatusIcon {
+    pub owner: Entity,
+    pub kind: StatusKind,
+}
+
 // ---- plugin ----
 pub struct HealthBarPlugin;
 
 impl Plugin for HealthBarPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<HealthBarSettings>()
-            .add_systems(Update, (update_health_bars, toggle_health_bars));
+            .add_systems(Update, (update_health_bars, update_status_icons, toggle_health_bars));
     }
 }
 
@@ -110,10 +121,53 @@ fn spawn_health_bar(commands: &mut Commands, owner: Entity, pos: Vec3, health_pc
     ));
 }
 
+pub fn update_status_icons(
+    mut commands: Commands,
+    owner_query: Query<(Entity, &StatusEffects, &Transform)>,
+    mut icon_query: Query<(Entity, &StatusIcon, &mut Transform, &mut Sprite), Without<StatusEffects>>,
+    settings: Res<HealthBarSettings>,
+) {
+    let mut shown = HashSet::new();
+
+    for (icon_entity, icon, mut icon_tf, mut sprite) in icon_query.iter_mut() {
+        let Ok((_, statuses, owner_tf)) = owner_query.get(icon.owner) else {
+            commands.entity(icon_entity).despawn();
+            continue;
+        };
+        let Some(index) = statuses.effects.iter().position(|effect| effect.kind == icon.kind) else {
+            commands.entity(icon_entity).despawn();
+            continue;
+        };
+
+        // Pips sit centered in a row; stacks make them a little bigger
+        let count = statuses.effects.len() as f32;
+        let x = (index as f32 - (count - 1.0) / 2.0) * (STATUS_ICON_SIZE + 1.0);
+        icon_tf.translation = owner_tf.translation + Vec3::new(x, STATUS_ICON_OFFSET, 1.0);
+        let size = STATUS_ICON_SIZE + statuses.effects[index].stacks.saturating_sub(1) as f32;
+        sprite.custom_size = Some(Vec2::splat(size));
+        shown.insert((icon.owner, icon.kind));
+    }
+
+    for (owner, statuses, owner_tf) in &owner_query {
+        for effect in statuses.effects.iter().filter(|effect| !shown.contains(&(owner, effect.kind))) {
+            commands.spawn((
+                Sprite {
+                    color: effect.kind.color(),
+                    custom_size: Some(Vec2::splat(STATUS_ICON_SIZE)),
+                    ..default()
+                },
+                Transform::from_translation(owner_tf.translation + Vec3::new(0.0, STATUS_ICON_OFFSET, 1.0)),
+                if settings.enabled { Visibility::Visible } else { Visibility::Hidden },
+                StatusIcon { owner, kind: effect.kind },
+            ));
+        }
+    }
+}
+
 pub fn toggle_health_bars(
     keys: Res<ButtonInput<KeyCode>>,
     mut settings: ResMut<HealthBarSettings>,
-    mut bar_q: Query<&mut Visibility, With<HealthBar>>,
+    mut bar_q: Query<&mut Visibility, Or<(With<HealthBar>, With<StatusIcon>)>>,
 ) {
     if keys.just_pressed(KeyCode::KeyH) {
         settings.enabled = !settings.enabled;
@@ -3,6 +3,8 @@ use crate::core::state::GameStats;
 use crate::game::spawning::WaveManager;
 use crate::game::player::Player;
 use crate::game::combat::Health;
+use crate::game::combat::modifiers::StatModifiers;
+use crate::game::enemy::{Boss, Enemy};
 use crate::systems::combo::ComboTracker;
 use crate::systems::shop::PlayerCurrency;
 
@@ -13,7 +15,7 @@ impl Plugin for HUDPlugin {
         app
             .init_resource::<GameStats>()
             .add_systems(Startup, setup_hud)
-            .add_systems(Update, update_hud);
+            .add_systems(Update, (update_hud, update_boss_bar, update_buff_icons));
     }
 }
 
@@ -27,6 +29,21 @@ struct WaveText;
 struct ComboText;
 #[derive(Component)]
 struct CurrencyText;
+#[derive(Component)]
+struct BossBar;
+#[derive(Component)]
+struct BossBarFill;
+#[derive(Component)]
+struct BossBarText;
+#[derive(Component)]
+struct BuffBar;
+/// One active modifier on the player, by its index in StatModifiers
+#[derive(Component)]
+struct BuffIcon {
+    index: usize,
+    source: String,       // What the icon was built for, to notice a different buff taking its index
+    label: &'static str,
+}
 
 fn setup_hud(mut commands: Commands) {
     // Score (top-left)
@@ -98,6 +115,58 @@ fn setup_hud(mut commands: Commands) {
         },
         CurrencyText,
     ));
+
+    // Active buffs (bottom-left, above health)
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(44.0),
+            left: Val::Px(10.0),
+            column_gap: Val::Px(6.0),
+            ..default()
+        },
+        BuffBar,
+    ));
+
+    // Boss health (top-center, below the combo), hidden until a boss is alive
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(50.0),
+            left: Val::Percent(25.0),
+            width: Val::Percent(50.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        Visibility::Hidden,
+        BossBar,
+    )).with_children(|parent| {
+        parent.spawn((
+            Text::new(""),
+            TextFont { font_size: 20.0, ..default() },
+            TextColor(Color::WHITE),
+            BossBarText,
+        ));
+        parent.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(14.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+        )).with_children(|bar| {
+            bar.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.8, 0.1, 0.1)),
+                BossBarFill,
+            ));
+        });
+    });
 }
 
 fn update_hud(
@@ -154,3 +223,78 @@ fn update_hud(
         }
     }
 }
+
+fn update_boss_bar(
+    boss_q: Query<(&Boss, &Enemy, &Health, Option<&Name>)>,
+    mut bar_q: Query<&mut Visibility, With<BossBar>>,
+    mut fill_q: Query<(&mut Node, &mut BackgroundColor), With<BossBarFill>>,
+    text_root: Query<Entity, With<BossBarText>>,
+    mut writer: TextUiWriter,
+) {
+    let Ok(mut visibility) = bar_q.single_mut() else { return };
+    let Some((boss, enemy, health, name)) = boss_q.iter().next() else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    *visibility = Visibility::Inherited;
+
+    if let Ok((mut node, mut color)) = fill_q.single_mut() {
+        node.width = Val::Percent(health.percentage().clamp(0.0, 1.0) * 100.0);
+        color.0 = if boss.enraged {
+            Color::srgb(1.0, 0.4, 0.0)
+        } else {
+            Color::srgb(0.8, 0.1, 0.1)
+        };
+    }
+    if let Ok(root) = text_root.single() {
+        let enraged = if boss.enraged { " - ENRAGED" } else { "" };
+        let name = name.map_or(enemy.enemy_type.display_name(), |name| name.as_str());
+        *writer.text(root, 0) = format!("{} (Phase {}){}", name, boss.phase, enraged);
+    }
+}
+
+fn update_buff_icons(
+    mut commands: Commands,
+    player_q: Query<&StatModifiers, With<Player>>,
+    bar_q: Query<Entity, With<BuffBar>>,
+    icon_q: Query<(Entity, &BuffIcon)>,
+    mut writer: TextUiWriter,
+) {
+    let Ok(bar) = bar_q.single() else { return };
+    let modifiers = player_q.single().map_or(&[][..], |modifiers| modifiers.modifiers.as_slice());
+
+    // Rebuild when buffs come or go, otherwise just count down
+    let stale = icon_q.iter().count() != modifiers.len()
+        || icon_q.iter().any(|(_, icon)| modifiers.get(icon.index).is_none_or(|timed| {
+            timed.source != icon.source || timed.modifier.label() != icon.label
+        }));
+    if stale {
+        for (entity, _) in icon_q.iter() {
+            commands.entity(entity).despawn();
+        }
+        for (index, timed) in modifiers.iter().enumerate() {
+            commands.entity(bar).with_child((
+                Text::new(""),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::BLACK),
+                Node {
+                    padding: UiRect::axes(Val::Px(4.0), Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(timed.modifier.color()),
+                BuffIcon {
+                    index,
+                    source: timed.source.clone(),
+                    label: timed.modifier.label(),
+                },
+            ));
+        }
+        return;
+    }
+
+    for (entity, icon) in icon_q.iter() {
+        if let Some(timed) = modifiers.get(icon.index) {
+            *writer.text(entity, 0) = format!("{} {:.1}s", timed.modifier.label(), timed.timer.remaining_secs());
+        }
+    }
+}
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use bevy::ui::*;
 use crate::entities::powerup::{PowerUpSlots, PowerUpType};
 use crate::game::abilities::{ActiveAbilities, AbilityRegistry, BodyPart};
+use crate::game::abilities::synergies::{ActiveSynergies, SynergyTable};
+use crate::core::save_system::SaveData;
 use crate::game::player::Player;
 use std::f32::consts::PI;
 
@@ -15,6 +17,8 @@ impl Plugin for PowerUpDisplayPlugin {
             .add_systems(Update, (
                 update_powerup_display.run_if(in_state(crate::core::state::GameState::Playing)),
                 update_cooldown_timers.run_if(in_state(crate::core::state::GameState::Playing)),
+                update_synergy_display.run_if(in_state(crate::core::state::GameState::Playing)),
+                update_slot_levels.run_if(in_state(crate::core::state::GameState::Playing)),
             ));
     }
 }
@@ -36,9 +40,17 @@ struct CooldownSegment {
     segment_index: usize,
 }
 
+#[derive(Component)]
+struct SlotLevelText {
+    slot_index: usize,
+}
+
 #[derive(Component)]
 pub struct PowerUpContainer;
 
+#[derive(Component)]
+struct SynergyText;
+
 #[derive(Component)]
 pub struct FruitDisplay {
     pub slot_index: usize,
@@ -92,6 +104,14 @@ fn setup_powerup_display_internal(
             PowerUpContainer,
         ))
         .with_children(|parent| {
+            // Active fruit synergies and how many have been found
+            parent.spawn((
+                Text::new(""),
+                TextFont { font_size: 11.0, ..default() },
+                TextColor(Color::srgb(1.0, 0.85, 0.4)),
+                SynergyText,
+            ));
+            
             // Create 3 power-up slots (head, torso, legs)
             for i in 0..3 {
                 let (label, body_part) = match i {
@@ -149,6 +169,20 @@ fn setup_powerup_display_internal(
                             FruitDisplay { slot_index: i },
                         ));
                         
+                        // Ability level in the corner, blank at level 1
+                        slot_parent.spawn((
+                            Node {
+                                position_type: PositionType::Absolute,
+                                right: Val::Px(2.0),
+                                bottom: Val::Px(0.0),
+                                ..default()
+                            },
+                            Text::new(""),
+                            TextFont { font_size: 10.0, ..default() },
+                            TextColor(Color::srgb(1.0, 0.85, 0.4)),
+                            SlotLevelText { slot_index: i },
+                        ));
+                        
                         // Create circular cooldown segments container
                         let _cooldown_container = slot_parent.spawn((
                             Node {
@@ -247,6 +281,10 @@ fn update_cooldown_timers(
                     .map(|ability| ability.cooldown_timer.elapsed().as_secs_f32() / ability.cooldown_timer.duration().as_secs_f32())
                     .unwrap_or(1.0)
                     .min(1.0),
+                BodyPart::Ultimate => abilities.ultimate_ability.as_ref()
+                    .map(|ability| ability.cooldown_timer.elapsed().as_secs_f32() / ability.cooldown_timer.duration().as_secs_f32())
+                    .unwrap_or(1.0)
+                    .min(1.0),
             };
             
             // Calculate how many segments should be bright (clock sweep)
@@ -264,4 +302,64 @@ fn update_cooldown_timers(
             }
         }
     }
-}
\ No newline at end of file
+}
+
+fn update_synergy_display(
+    player_query: Query<(&ActiveAbilities, Option<&ActiveSynergies>), With<Player>>,
+    text_query: Query<Entity, With<SynergyText>>,
+    registry: Res<AbilityRegistry>,
+    table: Res<SynergyTable>,
+    save_data: Res<SaveData>,
+    mut writer: TextUiWriter,
+) {
+    let Ok((abilities, synergies)) = player_query.single() else { return };
+    let Ok(entity) = text_query.single() else { return };
+    
+    let mut lines = Vec::new();
+    if let Some(definition) = abilities.ultimate_ability.as_ref()
+        .and_then(|ability| registry.abilities.get(&ability.ability_id))
+    {
+        lines.push(format!("ULTIMATE [G]: {}", definition.name));
+    }
+    for pair in synergies.iter().flat_map(|synergies| &synergies.pairs).filter_map(|index| table.pairs.get(*index)) {
+        lines.push(format!("{}: {}", pair.name, pair.description));
+    }
+    lines.push(format!("Synergies found: {}/{}", save_data.discovered_synergies.len(), table.total()));
+    
+    *writer.text(entity, 0) = lines.join("\n");
+}
+
+/// Shows each slot's ability level, and a gold border on locked slots
+fn update_slot_levels(
+    player_query: Query<(&PowerUpSlots, &ActiveAbilities), With<Player>>,
+    mut slot_query: Query<(&PowerUpSlotUI, &mut BorderColor)>,
+    text_query: Query<(Entity, &SlotLevelText)>,
+    registry: Res<AbilityRegistry>,
+    mut writer: TextUiWriter,
+) {
+    let Ok((powerup_slots, abilities)) = player_query.single() else { return };
+    
+    for (slot_ui, mut border) in slot_query.iter_mut() {
+        let locked = powerup_slots.slots.get(slot_ui.slot_index).is_some_and(|slot| slot.locked);
+        *border = if locked {
+            BorderColor(Color::srgb(1.0, 0.8, 0.2))
+        } else {
+            BorderColor(Color::srgb(0.5, 0.5, 0.5))
+        };
+    }
+    
+    for (entity, level_text) in text_query.iter() {
+        let ability = match level_text.slot_index {
+            0 => abilities.head_ability.as_ref(),
+            1 => abilities.torso_ability.as_ref(),
+            2 => abilities.legs_ability.as_ref(),
+            _ => None,
+        };
+        let text = match ability.and_then(|ability| Some((ability, registry.abilities.get(&ability.ability_id)?))) {
+            Some((ability, definition)) if ability.level >= definition.max_level() && ability.level > 1 => "MAX".to_string(),
+            Some((ability, _)) if ability.level > 1 => format!("Lv{}", ability.level),
+            _ => String::new(),
+        };
+        *writer.text(entity, 0) = text;
+    }
+}
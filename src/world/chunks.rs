@@ -0,0 +1,212 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::collections::HashMap;
+use bevy_ecs_tilemap::prelude::TilePos;
+use crate::core::camera::MainCamera;
+use crate::core::config::GameConfig;
+use crate::game::player::Player;
+use crate::world::tilemap::{TileType, TilemapConfig};
+use crate::world::level_loader::spawn_tilemap;
+use crate::world::tile_grid::TileGrid;
+
+pub struct ChunkStreamingPlugin;
+
+impl Plugin for ChunkStreamingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ChunkManager>()
+            .add_systems(Update, stream_chunks);
+    }
+}
+
+/// Streams an endless arena around the camera in `GameConfig::chunk_size` square chunks
+#[derive(Resource)]
+pub struct ChunkManager {
+    pub active: bool,
+    pub seed: u64,
+    pub view_distance: i32,        // Chunks kept loaded in each direction around the camera
+    pub unload_distance: i32,      // Chunks farther than this are despawned
+    pub max_spawns_per_frame: usize,
+    pub loaded: HashMap<IVec2, Entity>,    // Tilemap entity per chunk
+    tileset: Option<Handle<Image>>,
+}
+
+impl Default for ChunkManager {
+    fn default() -> Self {
+        Self {
+            active: false,
+            seed: 0,
+            view_distance: 1,
+            unload_distance: 2,
+            max_spawns_per_frame: 2,
+            loaded: HashMap::new(),
+            tileset: None,
+        }
+    }
+}
+
+impl ChunkManager {
+    /// Starts streaming a new arena. Any previously loaded chunks must already have been despawned.
+    pub fn begin(&mut self, seed: u64) {
+        self.reset();
+        self.active = true;
+        self.seed = seed;
+        println!("Starting endless arena with seed {}", seed);
+    }
+
+    /// Stops streaming and forgets loaded chunks (their tiles are removed with the rest of the level)
+    pub fn reset(&mut self) {
+        self.active = false;
+        self.loaded.clear();
+    }
+}
+
+/// Chunk coordinate containing a world position
+pub fn world_to_chunk(position: Vec2, chunk_world_size: f32) -> IVec2 {
+    (position / chunk_world_size).floor().as_ivec2()
+}
+
+fn stream_chunks(
+    mut commands: Commands,
+    mut chunk_manager: ResMut<ChunkManager>,
+    game_config: Res<GameConfig>,
+    mut tilemap_config: ResMut<TilemapConfig>,
+    mut grid: ResMut<TileGrid>,
+    asset_server: Res<AssetServer>,
+    camera_q: Query<&Transform, (With<MainCamera>, Without<Player>)>,
+    mut player_q: Query<&mut Transform, With<Player>>,
+) {
+    if !chunk_manager.active {
+        return;
+    }
+
+    let tile_size = game_config.tile_size;
+    let chunk_size = game_config.chunk_size.max(1);
+    let chunk_world_size = chunk_size as f32 * tile_size;
+    if tilemap_config.tile_size != tile_size {
+        tilemap_config.tile_size = tile_size;
+    }
+
+    // A fresh arena starts with the player in the cleared area around the origin
+    let fresh_arena = chunk_manager.loaded.is_empty();
+    if fresh_arena {
+        grid.reset(tile_size, Vec2::ZERO);
+        for mut player_tf in player_q.iter_mut() {
+            player_tf.translation.x = 0.0;
+            player_tf.translation.y = 0.0;
+        }
+    }
+    let Ok(camera_tf) = camera_q.single() else { return };
+
+    let tileset = chunk_manager.tileset
+        .get_or_insert_with(|| asset_server.load("sprites/tileset_16x16_32px.png"))
+        .clone();
+
+    // The camera still sits where the last level ended until it catches up with the player
+    let center = if fresh_arena {
+        IVec2::ZERO
+    } else {
+        world_to_chunk(camera_tf.translation.truncate(), chunk_world_size)
+    };
+
+    // Despawn chunks that drifted out of range
+    let unload_distance = chunk_manager.unload_distance;
+    let far_chunks: Vec<IVec2> = chunk_manager.loaded.keys()
+        .filter(|coord| (**coord - center).abs().max_element() > unload_distance)
+        .copied()
+        .collect();
+    for coord in far_chunks {
+        if let Some(tilemap) = chunk_manager.loaded.remove(&coord) {
+            commands.entity(tilemap).despawn();
+        }
+        let first_cell = coord * chunk_size as i32;
+        for y in 0..chunk_size as i32 {
+            for x in 0..chunk_size as i32 {
+                grid.remove(first_cell + IVec2::new(x, y));
+            }
+        }
+    }
+
+    // Spawn missing chunks nearest to the camera first, a few per frame to avoid hitches
+    let view_distance = chunk_manager.view_distance;
+    let mut missing: Vec<IVec2> = (-view_distance..=view_distance)
+        .flat_map(|y| (-view_distance..=view_distance).map(move |x| center + IVec2::new(x, y)))
+        .filter(|coord| !chunk_manager.loaded.contains_key(coord))
+        .collect();
+    missing.sort_by_key(|coord| (*coord - center).abs().max_element());
+
+    for coord in missing.into_iter().take(chunk_manager.max_spawns_per_frame) {
+        let tiles = generate_chunk(chunk_manager.seed, coord, chunk_size)
+            .into_iter()
+            .enumerate()
+            .map(|(index, tile_type)| {
+                (TilePos { x: index as u32 % chunk_size, y: index as u32 / chunk_size }, tile_type)
+            });
+        let tilemap = spawn_tilemap(
+            &mut commands,
+            &mut grid,
+            coord.as_vec2() * chunk_world_size + Vec2::splat(tile_size * 0.5),
+            UVec2::splat(chunk_size),
+            tiles,
+            tileset.clone(),
+            tile_size,
+        );
+        chunk_manager.loaded.insert(coord, tilemap);
+    }
+}
+
+/// Deterministic contents of one chunk, row-major from its bottom-left corner.
+/// The one tile border is always open ground so neighbouring chunks stay connected.
+pub fn generate_chunk(seed: u64, coord: IVec2, chunk_size: u32) -> Vec<TileType> {
+    let chunk_seed = seed
+        ^ (coord.x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (coord.y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    let mut rng = StdRng::seed_from_u64(chunk_seed);
+    let size = chunk_size as usize;
+
+    // Ground cover
+    let mut tiles: Vec<TileType> = (0..size * size)
+        .map(|_| match rng.gen_range(0..10) {
+            0 => TileType::Grass,
+            1 => TileType::Stone,
+            _ => TileType::Floor,
+        })
+        .collect();
+
+    // Keep the arena around the origin clear for the player's start
+    if coord == IVec2::ZERO || coord == IVec2::new(-1, -1) || coord == IVec2::new(-1, 0) || coord == IVec2::new(0, -1) {
+        return tiles;
+    }
+    if size < 6 {
+        return tiles;
+    }
+
+    let place_patch = |tiles: &mut Vec<TileType>, rng: &mut StdRng, tile_type: TileType, max_size: usize| {
+        let w = rng.gen_range(1..=max_size.min(size - 4));
+        let h = rng.gen_range(1..=max_size.min(size - 4));
+        let x0 = rng.gen_range(2..=size - 2 - w);
+        let y0 = rng.gen_range(2..=size - 2 - h);
+        for y in y0..y0 + h {
+            for x in x0..x0 + w {
+                tiles[y * size + x] = tile_type;
+            }
+        }
+    };
+
+    // Scattered pillars to break line of sight, kept small so nothing gets enclosed
+    for _ in 0..rng.gen_range(0..=3) {
+        place_patch(&mut tiles, &mut rng, TileType::Wall, 2);
+    }
+    if rng.gen::<f32>() < 0.3 {
+        place_patch(&mut tiles, &mut rng, TileType::Water, 5);
+    }
+    if rng.gen::<f32>() < 0.25 {
+        place_patch(&mut tiles, &mut rng, TileType::Spike, 3);
+    }
+    if rng.gen::<f32>() < 0.1 {
+        place_patch(&mut tiles, &mut rng, TileType::Lava, 2);
+    }
+
+    tiles
+}
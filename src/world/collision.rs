@@ -1,7 +1,8 @@
 use bevy::prelude::*;
-use crate::world::tilemap::Tile;
-use crate::game::movement::{Velocity, Collider};
-use crate::game::player::Player;
+use crate::world::tilemap::TilemapConfig;
+use crate::world::tile_grid::TileGrid;
+use crate::game::movement::{Velocity, Collider, Static};
+use crate::game::abilities::projectile_system::Projectile;
 
 pub struct TileCollisionPlugin;
 
@@ -11,71 +12,64 @@ impl Plugin for TileCollisionPlugin {
     }
 }
 
+// Pushes every moving actor out of the wall tiles it overlaps, zeroing only the blocked axis so it slides along walls
 fn check_tile_collisions(
-    mut player_query: Query<(&mut Transform, &Collider, &mut Velocity), With<Player>>,
-    tile_query: Query<(&Transform, &Tile), (With<Tile>, Without<Player>)>,
+    mut actor_query: Query<(&mut Transform, &Collider, &mut Velocity), (Without<Static>, Without<Projectile>)>,
+    grid: Res<TileGrid>,
+    config: Res<TilemapConfig>,
 ) {
-    let Ok((mut player_transform, player_collider, mut velocity)) = player_query.single_mut() else { return };
+    let tile_half_size = Vec2::splat(config.tile_size / 2.0);
     
-    let player_pos = player_transform.translation.truncate();
-    let player_half_size = player_collider.size / 2.0;
-    
-    for (tile_transform, tile) in tile_query.iter() {
-        // Skip walkable tiles
-        if tile.walkable {
-            continue;
-        }
-        
-        let tile_pos = tile_transform.translation.truncate();
-        let tile_half_size = Vec2::splat(16.0); // Half of 32x32 tile
+    for (mut transform, collider, mut velocity) in actor_query.iter_mut() {
+        let half_size = collider.size / 2.0;
+        let center_cell = grid.world_to_cell(transform.translation.truncate());
+        // Cells close enough to overlap this collider
+        let reach = (half_size / config.tile_size).ceil().as_ivec2();
         
-        // Check collision
-        if (player_pos.x - player_half_size.x < tile_pos.x + tile_half_size.x) &&
-           (player_pos.x + player_half_size.x > tile_pos.x - tile_half_size.x) &&
-           (player_pos.y - player_half_size.y < tile_pos.y + tile_half_size.y) &&
-           (player_pos.y + player_half_size.y > tile_pos.y - tile_half_size.y) {
-            
-            // Calculate overlap
-            let overlap_x = (player_half_size.x + tile_half_size.x) - (player_pos.x - tile_pos.x).abs();
-            let overlap_y = (player_half_size.y + tile_half_size.y) - (player_pos.y - tile_pos.y).abs();
-            
-            // Resolve collision by moving player away from tile
-            if overlap_x < overlap_y {
-                // Horizontal collision
-                if player_pos.x < tile_pos.x {
-                    player_transform.translation.x = tile_pos.x - tile_half_size.x - player_half_size.x;
-                } else {
-                    player_transform.translation.x = tile_pos.x + tile_half_size.x + player_half_size.x;
+        for dy in -reach.y..=reach.y {
+            for dx in -reach.x..=reach.x {
+                let cell = center_cell + IVec2::new(dx, dy);
+                // Skip walkable tiles
+                if grid.is_cell_walkable(cell) {
+                    continue;
                 }
-                velocity.0.x = 0.0;
-            } else {
-                // Vertical collision
-                if player_pos.y < tile_pos.y {
-                    player_transform.translation.y = tile_pos.y - tile_half_size.y - player_half_size.y;
-                } else {
-                    player_transform.translation.y = tile_pos.y + tile_half_size.y + player_half_size.y;
+                let tile_pos = grid.cell_to_world(cell);
+                let pos = transform.translation.truncate();
+                
+                // Check collision
+                if (pos.x - half_size.x < tile_pos.x + tile_half_size.x) &&
+                   (pos.x + half_size.x > tile_pos.x - tile_half_size.x) &&
+                   (pos.y - half_size.y < tile_pos.y + tile_half_size.y) &&
+                   (pos.y + half_size.y > tile_pos.y - tile_half_size.y) {
+                    
+                    // Calculate overlap
+                    let overlap_x = (half_size.x + tile_half_size.x) - (pos.x - tile_pos.x).abs();
+                    let overlap_y = (half_size.y + tile_half_size.y) - (pos.y - tile_pos.y).abs();
+                    
+                    // Resolve collision by moving the actor away from tile
+                    if overlap_x < overlap_y {
+                        // Horizontal collision
+                        if pos.x < tile_pos.x {
+                            transform.translation.x = tile_pos.x - tile_half_size.x - half_size.x;
+                        } else {
+                            transform.translation.x = tile_pos.x + tile_half_size.x + half_size.x;
+                        }
+                        velocity.0.x = 0.0;
+                    } else {
+                        // Vertical collision
+                        if pos.y < tile_pos.y {
+                            transform.translation.y = tile_pos.y - tile_half_size.y - half_size.y;
+                        } else {
+                            transform.translation.y = tile_pos.y + tile_half_size.y + half_size.y;
+                        }
+                        velocity.0.y = 0.0;
+                    }
                 }
-                velocity.0.y = 0.0;
             }
         }
     }
 }
 
-pub fn is_position_walkable(
-    position: Vec2,
-    tile_query: &Query<(&Transform, &Tile), With<Tile>>,
-) -> bool {
-    for (tile_transform, tile) in tile_query.iter() {
-        let tile_pos = tile_transform.translation.truncate();
-        let tile_half_size = Vec2::splat(16.0);
-        
-        // Check if position is within this tile
-        if (position.x >= tile_pos.x - tile_half_size.x) &&
-           (position.x <= tile_pos.x + tile_half_size.x) &&
-           (position.y >= tile_pos.y - tile_half_size.y) &&
-           (position.y <= tile_pos.y + tile_half_size.y) {
-            return tile.walkable;
-        }
-    }
-    true // Default to walkable if no tile found
+pub fn is_position_walkable(position: Vec2, grid: &TileGrid) -> bool {
+    grid.is_walkable(position)
 }
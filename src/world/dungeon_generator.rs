@@ -0,0 +1,470 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::collections::VecDeque;
+use crate::world::tilemap::{Tile, TileType};
+use crate::world::level_asset::{LevelAsset, LevelKey, LevelObject};
+use crate::world::level_loader::InteractionType;
+
+/// Settings for the procedural dungeon generator
+#[derive(Clone)]
+pub struct DungeonConfig {
+    pub width: usize,
+    pub height: usize,
+    pub max_rooms: u32,
+    pub room_attempts: u32,
+    pub min_room_size: usize,
+    pub max_room_size: usize,
+    pub tile_size: f32,
+    pub tileset: String,
+    // Per-room chances for each feature
+    pub door_chance: f32,
+    pub water_chance: f32,
+    pub lava_chance: f32,
+    pub spike_chance: f32,
+    pub chest_chance: f32,
+    pub locked_chest_chance: f32, // Per level: lock one chest and drop its key in the spawn room
+}
+
+impl Default for DungeonConfig {
+    fn default() -> Self {
+        Self {
+            width: 60,
+            height: 40,
+            max_rooms: 10,
+            room_attempts: 200,
+            min_room_size: 5,
+            max_room_size: 12,
+            tile_size: 32.0,
+            tileset: "sprites/tileset_16x16_32px.png".to_string(),
+            door_chance: 0.5,
+            water_chance: 0.25,
+            lava_chance: 0.2,
+            spike_chance: 0.3,
+            chest_chance: 0.35,
+            locked_chest_chance: 0.5,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Room {
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+}
+
+impl Room {
+    fn center(&self) -> UVec2 {
+        UVec2::new((self.x + self.w / 2) as u32, (self.y + self.h / 2) as u32)
+    }
+
+    /// Overlap test with a one tile gap so rooms never share walls
+    fn intersects(&self, other: &Room) -> bool {
+        self.x <= other.x + other.w
+            && self.x + self.w >= other.x
+            && self.y <= other.y + other.h
+            && self.y + self.h >= other.y
+    }
+
+    fn contains(&self, cell: UVec2) -> bool {
+        let (x, y) = (cell.x as usize, cell.y as usize);
+        x >= self.x && x < self.x + self.w && y >= self.y && y < self.y + self.h
+    }
+}
+
+struct Grid {
+    width: usize,
+    height: usize,
+    tiles: Vec<TileType>,
+}
+
+impl Grid {
+    fn get(&self, x: usize, y: usize) -> TileType {
+        self.tiles[y * self.width + x]
+    }
+
+    fn set(&mut self, x: usize, y: usize, tile_type: TileType) {
+        self.tiles[y * self.width + x] = tile_type;
+    }
+
+    fn walkable(&self, x: usize, y: usize) -> bool {
+        Tile::new(self.get(x, y)).walkable
+    }
+
+    fn walkable_count(&self) -> usize {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.walkable(x, y))
+            .count()
+    }
+
+    /// Breadth-first search over walkable tiles, returning the step distance to every cell (None if unreachable)
+    fn distances_from(&self, start: UVec2) -> Vec<Option<u32>> {
+        let mut distances = vec![None; self.width * self.height];
+        let mut queue = VecDeque::new();
+        distances[start.y as usize * self.width + start.x as usize] = Some(0);
+        queue.push_back((start.x as usize, start.y as usize));
+
+        while let Some((x, y)) = queue.pop_front() {
+            let distance = distances[y * self.width + x].unwrap_or(0);
+            let neighbors = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbors {
+                if nx >= self.width || ny >= self.height || !self.walkable(nx, ny) {
+                    continue;
+                }
+                let index = ny * self.width + nx;
+                if distances[index].is_none() {
+                    distances[index] = Some(distance + 1);
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// True when every walkable tile can be reached from `start`
+    fn is_fully_connected(&self, start: UVec2) -> bool {
+        let reached = self.distances_from(start).iter().filter(|d| d.is_some()).count();
+        reached == self.walkable_count()
+    }
+}
+
+/// Generates a rooms-and-corridors dungeon. The same seed and config always produce the same level.
+pub fn generate_dungeon(seed: u64, config: &DungeonConfig) -> LevelAsset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut grid = Grid {
+        width: config.width,
+        height: config.height,
+        tiles: vec![TileType::Wall; config.width * config.height],
+    };
+
+    // Place non-overlapping rooms inside the outer wall
+    let mut rooms: Vec<Room> = Vec::new();
+    for _ in 0..config.room_attempts {
+        if rooms.len() as u32 >= config.max_rooms {
+            break;
+        }
+        let w = rng.gen_range(config.min_room_size..=config.max_room_size);
+        let h = rng.gen_range(config.min_room_size..=config.max_room_size);
+        if w + 2 >= config.width || h + 2 >= config.height {
+            continue;
+        }
+        let room = Room {
+            x: rng.gen_range(1..config.width - w - 1),
+            y: rng.gen_range(1..config.height - h - 1),
+            w,
+            h,
+        };
+        if rooms.iter().any(|other| room.intersects(other)) {
+            continue;
+        }
+        carve_room(&mut grid, &room);
+        rooms.push(room);
+    }
+
+    // Fall back to a single room filling the map if nothing fit
+    if rooms.is_empty() {
+        let room = Room { x: 1, y: 1, w: config.width - 2, h: config.height - 2 };
+        carve_room(&mut grid, &room);
+        rooms.push(room);
+    }
+
+    // Chain every room to the previous one so the whole dungeon is connected
+    for i in 1..rooms.len() {
+        let from = rooms[i - 1].center();
+        let to = rooms[i].center();
+        carve_corridor(&mut grid, from, to, rng.gen_bool(0.5));
+    }
+
+    // Spawn in the first room, portal in the room farthest away by walking distance
+    let spawn = rooms[0].center();
+    let distances = grid.distances_from(spawn);
+    let portal_room = (1..rooms.len())
+        .max_by_key(|&i| {
+            let center = rooms[i].center();
+            distances[center.y as usize * grid.width + center.x as usize].unwrap_or(0)
+        })
+        .unwrap_or(0);
+    let portal = if portal_room == 0 {
+        // Single room: put the portal in the far corner
+        UVec2::new((rooms[0].x + rooms[0].w - 1) as u32, (rooms[0].y + rooms[0].h - 1) as u32)
+    } else {
+        rooms[portal_room].center()
+    };
+
+    place_doors(&mut grid, &rooms, config.door_chance, &mut rng);
+
+    let mut objects = Vec::new();
+    for (index, room) in rooms.iter().enumerate() {
+        let keep_clear = [spawn, portal];
+        if index != 0 && index != portal_room {
+            if rng.gen::<f32>() < config.lava_chance {
+                place_pool(&mut grid, room, TileType::Lava, spawn, &keep_clear, &mut rng);
+            } else if rng.gen::<f32>() < config.water_chance {
+                place_pool(&mut grid, room, TileType::Water, spawn, &keep_clear, &mut rng);
+            }
+            if rng.gen::<f32>() < config.spike_chance {
+                place_spikes(&mut grid, room, &keep_clear, &mut rng);
+            }
+        }
+        if index != 0 && rng.gen::<f32>() < config.chest_chance {
+            if let Some(chest) = place_chest(&mut grid, room, spawn, &keep_clear, &mut rng) {
+                objects.push(LevelObject::new(InteractionType::Chest, chest));
+            }
+        }
+    }
+
+    // The key sits in the spawn room, which is always reachable
+    let mut keys = Vec::new();
+    let last_chest = objects.iter().rposition(|object| matches!(object.interaction_type, InteractionType::Chest));
+    if let Some(index) = last_chest.filter(|_| rng.gen::<f32>() < config.locked_chest_chance) {
+        if let Some(position) = random_floor_cell(&grid, &rooms[0], &[spawn, portal], &mut rng) {
+            let id = "chest_key".to_string();
+            objects[index].lock = Some(id.clone());
+            keys.push(LevelKey { id, position });
+        }
+    }
+
+    grid.set(portal.x as usize, portal.y as usize, TileType::Portal);
+    objects.push(LevelObject::new(InteractionType::Portal, portal));
+
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            if grid.get(x, y) == TileType::Door {
+                objects.push(LevelObject::new(InteractionType::Door, UVec2::new(x as u32, y as u32)));
+            }
+        }
+    }
+
+    // One enemy spawn marker per room, away from the player's room
+    let enemy_spawns = rooms.iter()
+        .skip(1)
+        .filter_map(|room| random_floor_cell(&grid, room, &[portal], &mut rng))
+        .collect();
+
+    LevelAsset {
+        name: format!("Generated Dungeon {}", seed),
+        tileset: config.tileset.clone(),
+        tile_size: config.tile_size,
+        width: grid.width,
+        height: grid.height,
+        tiles: grid.tiles.into_iter().map(Some).collect(),
+        player_spawn: Some(spawn),
+        enemy_spawns,
+        objects,
+        keys,
+    }
+}
+
+fn carve_room(grid: &mut Grid, room: &Room) {
+    for y in room.y..room.y + room.h {
+        for x in room.x..room.x + room.w {
+            grid.set(x, y, TileType::Floor);
+        }
+    }
+}
+
+/// Carves an L-shaped, one tile wide corridor between two cells
+fn carve_corridor(grid: &mut Grid, from: UVec2, to: UVec2, horizontal_first: bool) {
+    let (fx, fy, tx, ty) = (from.x as usize, from.y as usize, to.x as usize, to.y as usize);
+    let corner = if horizontal_first { (tx, fy) } else { (fx, ty) };
+
+    for (ax, ay, bx, by) in [(fx, fy, corner.0, corner.1), (corner.0, corner.1, tx, ty)] {
+        for y in ay.min(by)..=ay.max(by) {
+            for x in ax.min(bx)..=ax.max(bx) {
+                if grid.get(x, y) == TileType::Wall {
+                    grid.set(x, y, TileType::Floor);
+                }
+            }
+        }
+    }
+}
+
+/// Turns corridor cells entering a room into doors
+fn place_doors(grid: &mut Grid, rooms: &[Room], chance: f32, rng: &mut StdRng) {
+    for room in rooms {
+        // Cells of the one-tile ring just outside the room
+        let mut ring = Vec::new();
+        for x in room.x..room.x + room.w {
+            ring.push((x, room.y - 1));
+            ring.push((x, room.y + room.h));
+        }
+        for y in room.y..room.y + room.h {
+            ring.push((room.x - 1, y));
+            ring.push((room.x + room.w, y));
+        }
+
+        for (x, y) in ring {
+            if grid.get(x, y) != TileType::Floor || rooms.iter().any(|r| r.contains(UVec2::new(x as u32, y as u32))) {
+                continue;
+            }
+            // Only doorway-shaped cells: walls on both sides across the corridor
+            let horizontal_walls = x > 0 && x + 1 < grid.width
+                && grid.get(x - 1, y) == TileType::Wall && grid.get(x + 1, y) == TileType::Wall;
+            let vertical_walls = y > 0 && y + 1 < grid.height
+                && grid.get(x, y - 1) == TileType::Wall && grid.get(x, y + 1) == TileType::Wall;
+            if (horizontal_walls || vertical_walls) && rng.gen::<f32>() < chance {
+                grid.set(x, y, TileType::Door);
+            }
+        }
+    }
+}
+
+/// Places a pool strictly inside the room, leaving a walkable floor ring around it.
+/// Blocking pools (lava) are rolled back if they would cut the dungeon apart.
+fn place_pool(grid: &mut Grid, room: &Room, tile_type: TileType, spawn: UVec2, keep_clear: &[UVec2], rng: &mut StdRng) {
+    if room.w < 5 || room.h < 5 {
+        return;
+    }
+    let w = rng.gen_range(1..=room.w - 4);
+    let h = rng.gen_range(1..=room.h - 4);
+    let x0 = rng.gen_range(room.x + 2..=room.x + room.w - 2 - w);
+    let y0 = rng.gen_range(room.y + 2..=room.y + room.h - 2 - h);
+
+    let mut changed = Vec::new();
+    for y in y0..y0 + h {
+        for x in x0..x0 + w {
+            if keep_clear.contains(&UVec2::new(x as u32, y as u32)) {
+                continue;
+            }
+            changed.push((x, y, grid.get(x, y)));
+            grid.set(x, y, tile_type);
+        }
+    }
+
+    if !Tile::new(tile_type).walkable && !grid.is_fully_connected(spawn) {
+        for (x, y, previous) in changed {
+            grid.set(x, y, previous);
+        }
+    }
+}
+
+fn place_spikes(grid: &mut Grid, room: &Room, keep_clear: &[UVec2], rng: &mut StdRng) {
+    let count = rng.gen_range(2..=(room.w * room.h / 8).max(2));
+    for _ in 0..count {
+        if let Some(cell) = random_floor_cell(grid, room, keep_clear, rng) {
+            grid.set(cell.x as usize, cell.y as usize, TileType::Spike);
+        }
+    }
+}
+
+/// Chests go in a room corner; skipped if the corner would block a path
+fn place_chest(grid: &mut Grid, room: &Room, spawn: UVec2, keep_clear: &[UVec2], rng: &mut StdRng) -> Option<UVec2> {
+    let corners = [
+        (room.x, room.y),
+        (room.x + room.w - 1, room.y),
+        (room.x, room.y + room.h - 1),
+        (room.x + room.w - 1, room.y + room.h - 1),
+    ];
+    let (x, y) = corners[rng.gen_range(0..corners.len())];
+    let cell = UVec2::new(x as u32, y as u32);
+    if grid.get(x, y) != TileType::Floor || keep_clear.contains(&cell) {
+        return None;
+    }
+
+    grid.set(x, y, TileType::Chest);
+    if grid.is_fully_connected(spawn) {
+        Some(cell)
+    } else {
+        grid.set(x, y, TileType::Floor);
+        None
+    }
+}
+
+fn random_floor_cell(grid: &Grid, room: &Room, exclude: &[UVec2], rng: &mut StdRng) -> Option<UVec2> {
+    for _ in 0..20 {
+        let x = rng.gen_range(room.x..room.x + room.w);
+        let y = rng.gen_range(room.y..room.y + room.h);
+        let cell = UVec2::new(x as u32, y as u32);
+        if grid.get(x, y) == TileType::Floor && !exclude.contains(&cell) {
+            return Some(cell);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cells reachable from `start` over walkable tiles
+    fn reachable(level: &LevelAsset, start: UVec2) -> Vec<bool> {
+        let walkable = |x: usize, y: usize| level.tiles[y * level.width + x].is_some_and(|tile| Tile::new(tile).walkable);
+        let mut seen = vec![false; level.width * level.height];
+        let mut queue = VecDeque::from([(start.x as usize, start.y as usize)]);
+        seen[start.y as usize * level.width + start.x as usize] = true;
+
+        while let Some((x, y)) = queue.pop_front() {
+            for (nx, ny) in [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)] {
+                if nx >= level.width || ny >= level.height || seen[ny * level.width + nx] || !walkable(nx, ny) {
+                    continue;
+                }
+                seen[ny * level.width + nx] = true;
+                queue.push_back((nx, ny));
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn same_seed_gives_same_level() {
+        let config = DungeonConfig::default();
+        for seed in [0, 7, 12345] {
+            let first = generate_dungeon(seed, &config);
+            let second = generate_dungeon(seed, &config);
+            assert!(first.tiles == second.tiles, "seed {} produced different tiles", seed);
+            assert_eq!(first.player_spawn, second.player_spawn);
+            assert_eq!(first.enemy_spawns, second.enemy_spawns);
+        }
+    }
+
+    #[test]
+    fn different_seeds_give_different_levels() {
+        let config = DungeonConfig::default();
+        assert!(generate_dungeon(1, &config).tiles != generate_dungeon(2, &config).tiles);
+    }
+
+    #[test]
+    fn portal_is_reachable_from_spawn() {
+        let config = DungeonConfig::default();
+        for seed in 0..50 {
+            let level = generate_dungeon(seed, &config);
+            let spawn = level.player_spawn.expect("generated levels always have a spawn");
+            let portals: Vec<UVec2> = level.objects.iter()
+                .filter(|object| matches!(object.interaction_type, InteractionType::Portal))
+                .map(|object| object.position)
+                .collect();
+            assert!(!portals.is_empty(), "seed {} has no portal", seed);
+
+            let seen = reachable(&level, spawn);
+            for portal in portals {
+                assert!(seen[portal.y as usize * level.width + portal.x as usize], "seed {} portal can't be reached", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn every_lock_has_a_reachable_key() {
+        let config = DungeonConfig::default();
+        let mut locks = 0;
+        for seed in 0..50 {
+            let level = generate_dungeon(seed, &config);
+            let seen = reachable(&level, level.player_spawn.expect("generated levels always have a spawn"));
+            for lock in level.objects.iter().filter_map(|object| object.lock.as_ref()) {
+                locks += 1;
+                let key = level.keys.iter().find(|key| &key.id == lock);
+                let key = key.unwrap_or_else(|| panic!("seed {} has lock '{}' without a key", seed, lock));
+                assert!(seen[key.position.y as usize * level.width + key.position.x as usize], "seed {} key '{}' can't be reached", seed, lock);
+            }
+        }
+        assert!(locks > 0, "no seed produced a locked chest");
+    }
+}
@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::tiles::TileTextureIndex;
+use crate::core::state::PlayState;
+use crate::core::input::{Action, InputBuffer, buffer_input_system};
+use crate::game::player::Player;
+use crate::stages::stage_manager::StageManager;
+use crate::stages::stage_transition::StageCompleteEvent;
+use crate::systems::loot::{DropLootEvent, LootSource};
+use crate::world::level_loader::{Interactive, InteractionType, Wall};
+use crate::world::tile_grid::TileGrid;
+use crate::world::tilemap::{Tile, TileType};
+
+const INTERACT_RANGE: f32 = 1.5;     // In tiles, measured to the object's center
+const KEY_PICKUP_RANGE: f32 = 20.0;
+const DOOR_CLOSED_FRAME: usize = 1;  // Tileset offset from the open door
+const CHEST_OPEN_FRAMES: u32 = 3;    // Frames after the closed chest, played once
+const CHEST_FRAME_TIME: f32 = 0.08;
+pub const DEFAULT_CHEST_TABLE: &str = "chest";
+
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<KeyRing>()
+            .add_systems(Update, (
+                pick_up_keys,
+                handle_interactions
+                    .after(buffer_input_system)
+                    .run_if(in_state(PlayState::Exploring)),
+                animate_opening_chests,
+            ));
+    }
+}
+
+/// Keys the player has picked up on this level
+#[derive(Resource, Default)]
+pub struct KeyRing {
+    pub keys: Vec<String>,
+}
+
+/// A key lying on the map, spawned from the level file
+#[derive(Component)]
+pub struct KeyPickup {
+    pub id: String,
+}
+
+/// Plays a chest's opening frames on its tile
+#[derive(Component)]
+pub struct ChestOpening {
+    pub timer: Timer,
+    pub frames_left: u32,
+}
+
+/// Opens or shuts a door tile, updating the grid so collision and pathfinding follow
+pub fn set_door(commands: &mut Commands, grid: &mut TileGrid, cell: IVec2, entity: Entity, open: bool) {
+    let mut tile = Tile::new(TileType::Door);
+    tile.walkable = open;
+    if !open {
+        tile.tile_index += DOOR_CLOSED_FRAME;
+    }
+    grid.insert(cell, tile, entity);
+
+    let mut entity_commands = commands.entity(entity);
+    entity_commands.insert((tile, TileTextureIndex(tile.tile_index as u32)));
+    if open {
+        entity_commands.remove::<Wall>();
+    } else {
+        entity_commands.insert(Wall);
+    }
+}
+
+/// Key sprite parented to the level's tilemap, so it goes away with the level
+pub fn spawn_key(commands: &mut Commands, tilemap: Entity, id: &str, offset: Vec2) {
+    commands.spawn((
+        KeyPickup { id: id.to_string() },
+        Sprite {
+            color: Color::srgb(1.0, 0.85, 0.2),
+            custom_size: Some(Vec2::new(12.0, 6.0)),
+            ..default()
+        },
+        Transform::from_translation(offset.extend(5.0)),
+        ChildOf(tilemap),
+    ));
+}
+
+fn pick_up_keys(
+    mut commands: Commands,
+    player_q: Query<&Transform, With<Player>>,
+    key_q: Query<(Entity, &KeyPickup, &GlobalTransform)>,
+    mut key_ring: ResMut<KeyRing>,
+) {
+    let Ok(player_tf) = player_q.single() else { return };
+
+    for (entity, key, key_tf) in key_q.iter() {
+        if key_tf.translation().truncate().distance(player_tf.translation.truncate()) <= KEY_PICKUP_RANGE {
+            println!("Picked up the {} key", key.id);
+            key_ring.keys.push(key.id.clone());
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Uses the nearest door, chest or portal in reach when Interact is pressed
+fn handle_interactions(
+    mut commands: Commands,
+    player_q: Query<&Transform, With<Player>>,
+    mut interactive_q: Query<&mut Interactive>,
+    mut grid: ResMut<TileGrid>,
+    mut buffer: ResMut<InputBuffer>,
+    mut key_ring: ResMut<KeyRing>,
+    stage_manager: Res<StageManager>,
+    mut loot_events: EventWriter<DropLootEvent>,
+    mut stage_events: EventWriter<StageCompleteEvent>,
+    time: Res<Time>,
+) {
+    let Ok(player_tf) = player_q.single() else { return };
+    let position = player_tf.translation.truncate();
+    let player_cell = grid.world_to_cell(position);
+    let reach = grid.tile_size * INTERACT_RANGE;
+
+    let target = (-2..=2)
+        .flat_map(|x| (-2..=2).map(move |y| player_cell + IVec2::new(x, y)))
+        .filter_map(|cell| grid.get(cell).map(|tile| (cell, tile.entity)))
+        .filter(|(_, entity)| interactive_q.contains(*entity))
+        .map(|(cell, entity)| (cell, entity, grid.cell_to_world(cell).distance(position)))
+        .filter(|(.., distance)| *distance <= reach)
+        .min_by(|(.., a), (.., b)| a.total_cmp(b));
+    // Leave the press buffered; walking into reach shortly after still counts
+    let Some((cell, entity, _)) = target else { return };
+    if !buffer.consume(Action::Interact, time.elapsed_secs()) {
+        return;
+    }
+    let Ok(mut interactive) = interactive_q.get_mut(entity) else { return };
+
+    if let Some(lock) = interactive.lock.clone() {
+        let Some(index) = key_ring.keys.iter().position(|key| *key == lock) else {
+            println!("Locked: needs the {} key", lock);
+            return;
+        };
+        key_ring.keys.remove(index);
+        interactive.lock = None;
+        println!("Unlocked with the {} key", lock);
+    }
+
+    match interactive.interaction_type {
+        InteractionType::Door => {
+            // Never shut a door on the player
+            if interactive.open && cell == player_cell {
+                return;
+            }
+            interactive.open = !interactive.open;
+            set_door(&mut commands, &mut grid, cell, entity, interactive.open);
+        }
+        InteractionType::Chest => {
+            if interactive.open {
+                return;
+            }
+            interactive.open = true;
+            commands.entity(entity).insert(ChestOpening {
+                timer: Timer::from_seconds(CHEST_FRAME_TIME, TimerMode::Repeating),
+                frames_left: CHEST_OPEN_FRAMES,
+            });
+            let table = interactive.loot_table.clone().unwrap_or_else(|| DEFAULT_CHEST_TABLE.to_string());
+            loot_events.write(DropLootEvent {
+                position: grid.cell_to_world(cell).extend(5.0),
+                source: LootSource::Chest(table),
+                luck_bonus: 0.0,
+                rolls: 1,
+            });
+        }
+        InteractionType::Portal => {
+            stage_events.write(StageCompleteEvent {
+                stage: stage_manager.current_stage,
+            });
+        }
+    }
+}
+
+fn animate_opening_chests(
+    mut commands: Commands,
+    mut chest_q: Query<(Entity, &mut ChestOpening, &mut TileTextureIndex)>,
+    time: Res<Time>,
+) {
+    for (entity, mut opening, mut texture) in chest_q.iter_mut() {
+        opening.timer.tick(time.delta());
+        if opening.timer.just_finished() {
+            texture.0 += 1;
+            opening.frames_left -= 1;
+            if opening.frames_left == 0 {
+                commands.entity(entity).remove::<ChestOpening>();
+            }
+        }
+    }
+}
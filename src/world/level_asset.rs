@@ -0,0 +1,292 @@
+use bevy::prelude::*;
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use crate::world::tilemap::TileType;
+use crate::world::level_loader::InteractionType;
+
+/// A level loaded from `assets/levels/*.json`
+#[derive(Asset, TypePath, Clone)]
+pub struct LevelAsset {
+    pub name: String,
+    pub tileset: String,
+    pub tile_size: f32,
+    pub width: usize,
+    pub height: usize,
+    /// Row-major tile grid, row 0 is the top of the map. `None` cells are left empty.
+    pub tiles: Vec<Option<TileType>>,
+    pub player_spawn: Option<UVec2>,
+    pub enemy_spawns: Vec<UVec2>,
+    pub objects: Vec<LevelObject>,
+    pub keys: Vec<LevelKey>,
+}
+
+/// An interactive object placed on top of the tile grid
+#[derive(Clone)]
+pub struct LevelObject {
+    pub interaction_type: InteractionType,
+    pub position: UVec2,
+    pub lock: Option<String>,       // Key id needed before it can be used
+    pub loot_table: Option<String>, // Chest drop table, "chest" when unset
+}
+
+impl LevelObject {
+    pub fn new(interaction_type: InteractionType, position: UVec2) -> Self {
+        Self {
+            interaction_type,
+            position,
+            lock: None,
+            loot_table: None,
+        }
+    }
+}
+
+/// A key lying on the map, opening every object locked with the same id
+#[derive(Clone)]
+pub struct LevelKey {
+    pub id: String,
+    pub position: UVec2,
+}
+
+/// On-disk representation of a level file
+#[derive(Deserialize)]
+struct LevelFile {
+    name: String,
+    tileset: String,
+    tile_size: f32,
+    width: usize,
+    height: usize,
+    #[serde(default)]
+    legend: HashMap<char, TileType>,
+    tiles: Vec<String>,
+    #[serde(default)]
+    spawn_points: SpawnPointsFile,
+    #[serde(default)]
+    objects: Vec<LevelObjectFile>,
+    #[serde(default)]
+    keys: Vec<LevelKeyFile>,
+}
+
+#[derive(Deserialize, Default)]
+struct SpawnPointsFile {
+    player: Option<[u32; 2]>,
+    #[serde(default)]
+    enemies: Vec<[u32; 2]>,
+}
+
+#[derive(Deserialize)]
+struct LevelObjectFile {
+    kind: InteractionType,
+    position: [u32; 2],
+    lock: Option<String>,
+    loot_table: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LevelKeyFile {
+    id: String,
+    position: [u32; 2],
+}
+
+#[derive(Debug)]
+pub enum LevelLoadError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    InvalidTileSize(f32),
+    RowCount { expected: usize, found: usize },
+    RaggedRow { row: usize, expected: usize, found: usize },
+    UnknownGlyph { glyph: char, row: usize, column: usize },
+    OutOfBounds { what: &'static str, position: UVec2 },
+}
+
+impl fmt::Display for LevelLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelLoadError::Io(e) => write!(f, "could not read level file: {}", e),
+            LevelLoadError::Json(e) => write!(f, "invalid level json: {}", e),
+            LevelLoadError::InvalidTileSize(size) => write!(f, "tile_size must be positive, got {}", size),
+            LevelLoadError::RowCount { expected, found } => {
+                write!(f, "expected {} tile rows (height), found {}", expected, found)
+            }
+            LevelLoadError::RaggedRow { row, expected, found } => {
+                write!(f, "tile row {} has {} columns, expected {} (width)", row, found, expected)
+            }
+            LevelLoadError::UnknownGlyph { glyph, row, column } => {
+                write!(f, "unknown tile glyph '{}' at row {}, column {}", glyph, row, column)
+            }
+            LevelLoadError::OutOfBounds { what, position } => {
+                write!(f, "{} at ({}, {}) is outside the map", what, position.x, position.y)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LevelLoadError {}
+
+impl From<std::io::Error> for LevelLoadError {
+    fn from(e: std::io::Error) -> Self {
+        LevelLoadError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for LevelLoadError {
+    fn from(e: serde_json::Error) -> Self {
+        LevelLoadError::Json(e)
+    }
+}
+
+/// Glyphs understood by every level; a file's `legend` can add to or override these
+pub fn default_legend() -> HashMap<char, TileType> {
+    HashMap::from([
+        ('#', TileType::Wall),
+        ('.', TileType::Floor),
+        ('D', TileType::Door),
+        ('C', TileType::Chest),
+        ('^', TileType::Spike),
+        ('~', TileType::Water),
+        ('L', TileType::Lava),
+        ('P', TileType::Portal),
+        (',', TileType::Grass),
+        (':', TileType::Stone),
+    ])
+}
+
+impl LevelAsset {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LevelLoadError> {
+        let file: LevelFile = serde_json::from_slice(bytes)?;
+        Self::from_file(file)
+    }
+
+    fn from_file(file: LevelFile) -> Result<Self, LevelLoadError> {
+        if file.tile_size <= 0.0 {
+            return Err(LevelLoadError::InvalidTileSize(file.tile_size));
+        }
+        if file.tiles.len() != file.height {
+            return Err(LevelLoadError::RowCount { expected: file.height, found: file.tiles.len() });
+        }
+
+        let mut legend = default_legend();
+        legend.extend(file.legend);
+
+        let mut tiles = Vec::with_capacity(file.width * file.height);
+        for (row, line) in file.tiles.iter().enumerate() {
+            let columns = line.chars().count();
+            if columns != file.width {
+                return Err(LevelLoadError::RaggedRow { row, expected: file.width, found: columns });
+            }
+
+            for (column, glyph) in line.chars().enumerate() {
+                // Spaces are void: nothing is spawned there
+                if glyph == ' ' {
+                    tiles.push(None);
+                    continue;
+                }
+                match legend.get(&glyph) {
+                    Some(tile_type) => tiles.push(Some(*tile_type)),
+                    None => return Err(LevelLoadError::UnknownGlyph { glyph, row, column }),
+                }
+            }
+        }
+
+        let mut level = Self {
+            name: file.name,
+            tileset: file.tileset,
+            tile_size: file.tile_size,
+            width: file.width,
+            height: file.height,
+            tiles,
+            player_spawn: file.spawn_points.player.map(UVec2::from),
+            enemy_spawns: file.spawn_points.enemies.into_iter().map(UVec2::from).collect(),
+            objects: Vec::new(),
+            keys: Vec::new(),
+        };
+
+        if let Some(spawn) = level.player_spawn {
+            level.check_bounds("player spawn", spawn)?;
+        }
+        for spawn in &level.enemy_spawns {
+            level.check_bounds("enemy spawn", *spawn)?;
+        }
+
+        // Objects override whatever glyph sits underneath them
+        for object in file.objects {
+            let position = UVec2::from(object.position);
+            level.check_bounds("object", position)?;
+            let index = level.index(position);
+            level.tiles[index] = Some(object.kind.tile_type());
+            level.objects.push(LevelObject {
+                interaction_type: object.kind,
+                position,
+                lock: object.lock,
+                loot_table: object.loot_table,
+            });
+        }
+
+        for key in file.keys {
+            let position = UVec2::from(key.position);
+            level.check_bounds("key", position)?;
+            level.keys.push(LevelKey { id: key.id, position });
+        }
+
+        Ok(level)
+    }
+
+    fn check_bounds(&self, what: &'static str, position: UVec2) -> Result<(), LevelLoadError> {
+        if self.in_bounds(position) {
+            Ok(())
+        } else {
+            Err(LevelLoadError::OutOfBounds { what, position })
+        }
+    }
+
+    pub fn in_bounds(&self, cell: UVec2) -> bool {
+        (cell.x as usize) < self.width && (cell.y as usize) < self.height
+    }
+
+    fn index(&self, cell: UVec2) -> usize {
+        cell.y as usize * self.width + cell.x as usize
+    }
+
+    pub fn tile_at(&self, cell: UVec2) -> Option<TileType> {
+        if self.in_bounds(cell) {
+            self.tiles[self.index(cell)]
+        } else {
+            None
+        }
+    }
+
+    /// World-space center of a grid cell; the map is centered on the origin
+    pub fn cell_to_world(&self, cell: UVec2) -> Vec2 {
+        let origin_x = -(self.width as f32) * self.tile_size * 0.5 + self.tile_size * 0.5;
+        let origin_y = -(self.height as f32) * self.tile_size * 0.5 + self.tile_size * 0.5;
+        Vec2::new(
+            origin_x + cell.x as f32 * self.tile_size,
+            origin_y + (self.height as f32 - 1.0 - cell.y as f32) * self.tile_size,
+        )
+    }
+}
+
+#[derive(Default)]
+pub struct LevelAssetLoader;
+
+impl AssetLoader for LevelAssetLoader {
+    type Asset = LevelAsset;
+    type Settings = ();
+    type Error = LevelLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        LevelAsset::from_bytes(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
@@ -1,6 +1,16 @@
 use bevy::prelude::*;
-use crate::game::movement::Collider;
-use crate::world::tilemap::{Tile, TileType, TilemapConfig, AnimatedTile};
+use bevy_ecs_tilemap::prelude::*;
+use serde::Deserialize;
+use crate::game::player::Player;
+use crate::world::tilemap::{Tile, TileType, TilemapConfig};
+use crate::world::level_asset::LevelAsset;
+use crate::world::tile_grid::TileGrid;
+use crate::world::dungeon_generator::{generate_dungeon, DungeonConfig};
+use crate::world::chunks::ChunkManager;
+use crate::stages::stage_manager::StageManager;
+use crate::world::interactions::{self, KeyRing};
+
+pub const DEFAULT_LEVEL: &str = "levels/level1.json";
 
 #[derive(Component)]
 pub struct Wall;
@@ -8,203 +18,309 @@ pub struct Wall;
 #[derive(Component)]
 pub struct Interactive {
     pub interaction_type: InteractionType,
+    pub lock: Option<String>,       // Key id that must be used first
+    pub loot_table: Option<String>, // Chests only
+    pub open: bool,                 // Doors start open; chests start shut
+}
+
+impl Interactive {
+    pub fn new(interaction_type: InteractionType) -> Self {
+        Self {
+            interaction_type,
+            lock: None,
+            loot_table: None,
+            open: matches!(interaction_type, InteractionType::Door),
+        }
+    }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Deserialize)]
 pub enum InteractionType {
     Door,
     Chest,
     Portal,
 }
 
+impl InteractionType {
+    pub fn tile_type(&self) -> TileType {
+        match self {
+            InteractionType::Door => TileType::Door,
+            InteractionType::Chest => TileType::Chest,
+            InteractionType::Portal => TileType::Portal,
+        }
+    }
+}
+
+/// Where a stage's map comes from
+#[derive(Clone)]
+pub enum LevelSource {
+    File(String),     // Asset path, e.g. "levels/level1.json"
+    Generated(u64),   // Procedural dungeon seed
+    Endless(u64),     // Streamed arena seed, see ChunkManager
+}
+
+/// The level asset currently in play and whether its tiles have been spawned
+#[derive(Resource)]
+pub struct CurrentLevel {
+    pub handle: Handle<LevelAsset>,
+    pub spawned: bool,
+}
+
+impl CurrentLevel {
+    /// Starts playing `source`. Endless arenas have no level asset; their tiles are streamed in chunks instead.
+    pub fn start(
+        source: &LevelSource,
+        asset_server: &AssetServer,
+        levels: &mut Assets<LevelAsset>,
+        chunk_manager: &mut ChunkManager,
+    ) -> Self {
+        chunk_manager.reset();
+        match source {
+            LevelSource::File(path) => Self {
+                handle: asset_server.load(path.clone()),
+                spawned: false,
+            },
+            LevelSource::Generated(seed) => {
+                println!("Generating dungeon with seed {}", seed);
+                Self {
+                    handle: levels.add(generate_dungeon(*seed, &DungeonConfig::default())),
+                    spawned: false,
+                }
+            }
+            LevelSource::Endless(seed) => {
+                chunk_manager.begin(*seed);
+                Self {
+                    handle: Handle::default(),
+                    spawned: true,
+                }
+            }
+        }
+    }
+}
+
 pub fn load_level(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    config: Res<TilemapConfig>,
+    mut levels: ResMut<Assets<LevelAsset>>,
+    mut chunk_manager: ResMut<ChunkManager>,
+    stage_manager: Res<StageManager>,
 ) {
-    spawn_level(&mut commands, &asset_server, &mut texture_atlas_layouts, &config);
+    let source = stage_manager.current_level_source()
+        .cloned()
+        .unwrap_or_else(|| LevelSource::File(DEFAULT_LEVEL.to_string()));
+    commands.insert_resource(CurrentLevel::start(&source, &asset_server, &mut levels, &mut chunk_manager));
 }
 
 pub fn load_test_level(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    config: Res<TilemapConfig>,
 ) {
-    spawn_level(&mut commands, &asset_server, &mut texture_atlas_layouts, &config);
+    commands.insert_resource(CurrentLevel {
+        handle: asset_server.load(DEFAULT_LEVEL),
+        spawned: false,
+    });
+}
+
+/// Spawns the current level once its asset has finished loading, and respawns it when the file changes on disk
+pub fn spawn_loaded_level(
+    mut commands: Commands,
+    current_level: Option<ResMut<CurrentLevel>>,
+    levels: Res<Assets<LevelAsset>>,
+    mut level_events: EventReader<AssetEvent<LevelAsset>>,
+    asset_server: Res<AssetServer>,
+    mut config: ResMut<TilemapConfig>,
+    mut grid: ResMut<TileGrid>,
+    mut key_ring: ResMut<KeyRing>,
+    tilemaps: Query<Entity, With<TileStorage>>,
+    mut player_q: Query<&mut Transform, With<Player>>,
+) {
+    let Some(mut current_level) = current_level else { return };
+
+    for event in level_events.read() {
+        if event.is_modified(&current_level.handle) && current_level.spawned {
+            println!("Level file changed, reloading");
+            for e in tilemaps.iter() {
+                commands.entity(e).despawn();
+            }
+            current_level.spawned = false;
+        }
+    }
+
+    if current_level.spawned {
+        return;
+    }
+    let Some(level) = levels.get(&current_level.handle) else { return };
+
+    config.tile_size = level.tile_size;
+    key_ring.keys.clear();
+    spawn_level(&mut commands, level, &asset_server, &mut grid);
+    current_level.spawned = true;
+
+    if let Some(spawn) = level.player_spawn {
+        let spawn_pos = level.cell_to_world(spawn);
+        for mut player_tf in player_q.iter_mut() {
+            player_tf.translation.x = spawn_pos.x;
+            player_tf.translation.y = spawn_pos.y;
+        }
+    }
+
+    println!("Loaded level '{}' ({}x{})", level.name, level.width, level.height);
 }
 
 fn spawn_level(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    texture_atlas_layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
-    config: &Res<TilemapConfig>,
+    level: &LevelAsset,
+    asset_server: &AssetServer,
+    grid: &mut TileGrid,
 ) {
-    let level_data = r#"
-################################################
-#..............................................#
-#.....###...###...###.........####.............#
-#.....#.....#.#...#.#.........#..#.............#
-#.....###...#.#...###.........#..#.............#
-#.....#.....#.#...#.#.........####.............#
-#.....#.....###...#.#..........................#
-#..............................................#
-#...####....####....####....####....####.......#
-#...#..#....#..#....#..#....#..#....#..#.......#
-#...#..######..######..######..######..#.......#
-#...#..........................................#
-#...####....####....####....####....####.......#
-#..............................................#
-#.....CCCC......................CCCC...........#
-#..............................................#
-#...################....################.......#
-#..........................................^^^^#
-#..........................................^^^^#
-#..............................................#
-#...~~~~~..~~~~~..~~~~~..~~~~~..~~~~~..~~~~~...#
-#...~~~~~..~~~~~..~~~~~..~~~~~..~~~~~..~~~~~...#
-#..............................................#
-#..####....####....####....####....####....###.#
-#..#..#....#..#....#..#....#..#....#..#....#...#
-#..#..######..######..######..######..######...#
-#..............................................#
-#......^^^^....................................#
-#......^^^^....................................#
-################################################
-"#;
-
-    let lines: Vec<&str> = level_data
-        .lines()
-        .filter(|l| !l.trim().is_empty())
-        .collect();
-    
-    let height = lines.len();
-    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
-    
-    let tileset_handle: Handle<Image> = asset_server.load("sprites/tileset_16x16_32px.png");
-    let layout = TextureAtlasLayout::from_grid(
-        UVec2::new(config.tile_size as u32, config.tile_size as u32),
-        config.tileset_columns as u32,
-        config.tileset_rows as u32,
-        None,
-        None,
+    // Level rows run top to bottom, tilemap rows bottom to top
+    let tiles = (0..level.height as u32).flat_map(|y| (0..level.width as u32).map(move |x| UVec2::new(x, y)))
+        .filter_map(|cell| {
+            let tile_type = level.tile_at(cell)?;
+            Some((TilePos { x: cell.x, y: level.height as u32 - 1 - cell.y }, tile_type))
+        });
+
+    let origin = level.cell_to_world(UVec2::new(0, level.height as u32 - 1));
+    grid.reset(level.tile_size, origin - Vec2::splat(level.tile_size * 0.5));
+
+    let tilemap = spawn_tilemap(
+        commands,
+        grid,
+        origin,
+        UVec2::new(level.width as u32, level.height as u32),
+        tiles,
+        asset_server.load(&level.tileset),
+        level.tile_size,
     );
-    let layout_handle = texture_atlas_layouts.add(layout);
-    
-    let map_w = width as f32 * config.tile_size;
-    let map_h = height as f32 * config.tile_size;
-    let origin_x = -map_w * 0.5 + config.tile_size * 0.5;
-    let origin_y = -map_h * 0.5 + config.tile_size * 0.5;
-    
-    for (y, line) in lines.iter().enumerate() {
-        for (x, ch) in line.chars().enumerate() {
-            let tile_type = match ch {
-                '#' => Some(TileType::Wall),
-                '.' => Some(TileType::Floor),
-                'D' => Some(TileType::Door),
-                'C' => Some(TileType::Chest),
-                '^' => Some(TileType::Spike),
-                '~' => Some(TileType::Water),
-                _ => None,
-            };
-
-            if let Some(tile_type) = tile_type {
-                let tile = Tile::new(tile_type);
-                let tile_walkable = tile.walkable;  // Save walkable state before move
-
-                let world_pos = Vec3::new(
-                    origin_x + x as f32 * config.tile_size,
-                    origin_y + (height as f32 - 1.0 - y as f32) * config.tile_size,
-                    0.0,
-                );
-
-                let mut entity_commands = commands.spawn((
-                    Sprite {
-                        image: tileset_handle.clone(),
-                        texture_atlas: Some(TextureAtlas {
-                            layout: layout_handle.clone(),
-                            index: tile.tile_index,
-                        }),
-                        ..default()
-                    },
-                    Transform::from_translation(world_pos),
-                    tile,  // tile is moved here
-                ));
-
-                // Use the saved walkable state instead of tile.walkable
-                if !tile_walkable {
-                    entity_commands.insert(Wall);
-                    entity_commands.insert(Collider { size: Vec2::splat(config.tile_size) });
-                }
-                // Add AnimatedTile for water, lava, and portals
-                match tile_type {
-                    TileType::Water => {
-                        entity_commands.insert(AnimatedTile {
-                            frames: vec![45, 46, 47, 48],
-                            current_frame: 0,
-                            timer: Timer::from_seconds(0.5, TimerMode::Repeating),
-                        });
-                    }
-                    TileType::Lava => {
-                        entity_commands.insert(AnimatedTile {
-                            frames: vec![49, 50, 51, 52],
-                            current_frame: 0,
-                            timer: Timer::from_seconds(0.3, TimerMode::Repeating),
-                        });
-                    }
-                    TileType::Portal => {
-                        entity_commands.insert(AnimatedTile {
-                            frames: vec![53, 54, 55, 56],
-                            current_frame: 0,
-                            timer: Timer::from_seconds(0.2, TimerMode::Repeating),
-                        });
-                    }
-                    _ => {}
-                }
-                
-                // Add wall collider for non-walkable tiles
-                if !tile.walkable {
-                    entity_commands.insert(Wall);
-                    entity_commands.insert(Collider { size: Vec2::splat(config.tile_size) });
-                }
-                
-                // Add interactive components
-                match tile_type {
-                    TileType::Door => {
-                        entity_commands.insert(Interactive {
-                            interaction_type: InteractionType::Door,
-                        });
-                    }
-                    TileType::Chest => {
-                        entity_commands.insert(Interactive {
-                            interaction_type: InteractionType::Chest,
-                        });
-                    }
-                    TileType::Portal => {
-                        entity_commands.insert(Interactive {
-                            interaction_type: InteractionType::Portal,
-                        });
-                    }
-                    _ => {}
-                }
-            }
+
+    for spawn in &level.enemy_spawns {
+        let cell = grid.world_to_cell(level.cell_to_world(*spawn));
+        grid.spawn_points.push(cell);
+    }
+
+    // Locks and loot tables from the level file replace the tiles' plain Interactive
+    for object in level.objects.iter().filter(|object| object.lock.is_some() || object.loot_table.is_some()) {
+        let cell = grid.world_to_cell(level.cell_to_world(object.position));
+        let Some(entity) = grid.get(cell).map(|tile| tile.entity) else { continue };
+        let locked_door = object.lock.is_some() && matches!(object.interaction_type, InteractionType::Door);
+        commands.entity(entity).insert(Interactive {
+            lock: object.lock.clone(),
+            loot_table: object.loot_table.clone(),
+            open: !locked_door && matches!(object.interaction_type, InteractionType::Door),
+            ..Interactive::new(object.interaction_type)
+        });
+        if locked_door {
+            interactions::set_door(commands, grid, cell, entity, false);
         }
     }
+
+    for key in &level.keys {
+        interactions::spawn_key(commands, tilemap, &key.id, level.cell_to_world(key.position) - origin);
+    }
+}
+
+/// Spawns a square tilemap whose tile (0, 0) is centered on `origin` and records its tiles in the TileGrid.
+/// Tiles are children of the returned map entity.
+pub fn spawn_tilemap(
+    commands: &mut Commands,
+    grid: &mut TileGrid,
+    origin: Vec2,
+    size: UVec2,
+    tiles: impl IntoIterator<Item = (TilePos, TileType)>,
+    tileset: Handle<Image>,
+    tile_size: f32,
+) -> Entity {
+    let map_size = TilemapSize { x: size.x, y: size.y };
+    let tilemap_entity = commands.spawn_empty().id();
+    let mut storage = TileStorage::empty(map_size);
+
+    for (position, tile_type) in tiles {
+        let tile_entity = spawn_tile(commands, tilemap_entity, position, tile_type);
+        storage.set(&position, tile_entity);
+
+        let center = origin + Vec2::new(position.x as f32, position.y as f32) * tile_size;
+        grid.insert(grid.world_to_cell(center), Tile::new(tile_type), tile_entity);
+    }
+
+    commands.entity(tilemap_entity).insert(TilemapBundle {
+        grid_size: TilemapGridSize { x: tile_size, y: tile_size },
+        map_type: TilemapType::Square,
+        size: map_size,
+        storage,
+        texture: TilemapTexture::Single(tileset),
+        tile_size: TilemapTileSize { x: tile_size, y: tile_size },
+        transform: Transform::from_translation(origin.extend(0.0)),
+        anchor: TilemapAnchor::None,
+        ..default()
+    });
+
+    tilemap_entity
+}
+
+/// Spawns one map tile with its animation and interaction components
+fn spawn_tile(
+    commands: &mut Commands,
+    tilemap_entity: Entity,
+    position: TilePos,
+    tile_type: TileType,
+) -> Entity {
+    let tile = Tile::new(tile_type);
+
+    let mut entity_commands = commands.spawn((
+        TileBundle {
+            position,
+            texture_index: TileTextureIndex(tile.tile_index as u32),
+            tilemap_id: TilemapId(tilemap_entity),
+            ..default()
+        },
+        tile,
+        ChildOf(tilemap_entity),
+    ));
+
+    // Mark non-walkable tiles as walls
+    if !tile.walkable {
+        entity_commands.insert(Wall);
+    }
+
+    // Water, lava, and portals animate on the GPU
+    if let Some(animation) = tile.animation() {
+        entity_commands.insert(animation);
+    }
+
+    // Add interactive components
+    match tile_type {
+        TileType::Door => {
+            entity_commands.insert(Interactive::new(InteractionType::Door));
+        }
+        TileType::Chest => {
+            entity_commands.insert(Interactive::new(InteractionType::Chest));
+        }
+        TileType::Portal => {
+            entity_commands.insert(Interactive::new(InteractionType::Portal));
+        }
+        _ => {}
+    }
+
+    entity_commands.id()
 }
 
+// Despawning a tilemap also despawns its tiles
 pub fn cleanup_level(
     mut commands: Commands,
-    tiles: Query<Entity, With<Tile>>,
+    mut grid: ResMut<TileGrid>,
+    tilemaps: Query<Entity, With<TileStorage>>,
 ) {
-    for e in tiles.iter() {
+    for e in tilemaps.iter() {
         commands.entity(e).despawn();
     }
+    let tile_size = grid.tile_size;
+    grid.reset(tile_size, Vec2::ZERO);
 }
 
 pub fn despawn_level(
     commands: Commands,
-    tiles: Query<Entity, With<Tile>>,
+    grid: ResMut<TileGrid>,
+    tilemaps: Query<Entity, With<TileStorage>>,
 ) {
-    cleanup_level(commands, tiles);
+    cleanup_level(commands, grid, tilemaps);
 }
@@ -1,6 +1,13 @@
 pub mod tilemap;
 pub mod level_loader;
+pub mod level_asset;
+pub mod dungeon_generator;
+pub mod chunks;
+pub mod tile_grid;
+pub mod pathfinding;
 pub mod collision;
+pub mod spawn_placement;
+pub mod interactions;
 
 use bevy::prelude::*;
 
@@ -9,9 +16,14 @@ pub struct WorldPlugin;
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_asset::<level_asset::LevelAsset>()
+            .init_asset_loader::<level_asset::LevelAssetLoader>()
             .add_plugins((
                 tilemap::TilemapPlugin,
                 collision::TileCollisionPlugin,
+                chunks::ChunkStreamingPlugin,
+                pathfinding::PathfindingPlugin,
+                interactions::InteractionPlugin,
             ));
     }
 }
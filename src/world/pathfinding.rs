@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use crate::game::player::Player;
+use crate::world::tile_grid::TileGrid;
+use crate::world::tilemap::TileType;
+
+pub struct PathfindingPlugin;
+
+impl Plugin for PathfindingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<FlowField>()
+            .add_systems(Update, update_flow_field);
+    }
+}
+
+const NEIGHBORS: [IVec2; 8] = [
+    IVec2::new(1, 0),
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(0, -1),
+    IVec2::new(1, 1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+];
+
+/// Cost of stepping onto a tile; hazards cost more so enemies route around them when they can
+pub fn tile_cost(tile_type: TileType) -> f32 {
+    match tile_type {
+        TileType::Water => 3.0,
+        TileType::Spike => 6.0,
+        _ => 1.0,
+    }
+}
+
+// None for walls and for cells outside the map
+fn cell_cost(grid: &TileGrid, cell: IVec2) -> Option<f32> {
+    let tile = grid.get(cell)?;
+    tile.walkable.then(|| tile_cost(tile.tile_type))
+}
+
+// Walkable neighbours with their step cost. Diagonals may not cut wall corners.
+fn neighbors(grid: &TileGrid, cell: IVec2) -> impl Iterator<Item = (IVec2, f32)> + '_ {
+    NEIGHBORS.iter().filter_map(move |offset| {
+        let next = cell + *offset;
+        let cost = cell_cost(grid, next)?;
+        if offset.x != 0 && offset.y != 0 {
+            cell_cost(grid, cell + IVec2::new(offset.x, 0))?;
+            cell_cost(grid, cell + IVec2::new(0, offset.y))?;
+            return Some((next, cost * std::f32::consts::SQRT_2));
+        }
+        Some((next, cost))
+    })
+}
+
+// Min-heap entry for Dijkstra / A*
+#[derive(PartialEq)]
+struct OpenCell {
+    priority: f32,
+    cell: IVec2,
+}
+
+impl Eq for OpenCell {}
+
+impl Ord for OpenCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.total_cmp(&self.priority)
+    }
+}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Shared cost-to-player field over the walkable tiles, rebuilt whenever the player changes cell
+#[derive(Resource)]
+pub struct FlowField {
+    pub target: Option<IVec2>,
+    pub max_cost: f32, // Cells farther than this from the player are left out of the field
+    costs: HashMap<IVec2, f32>,
+}
+
+impl Default for FlowField {
+    fn default() -> Self {
+        Self {
+            target: None,
+            max_cost: 40.0,
+            costs: HashMap::new(),
+        }
+    }
+}
+
+impl FlowField {
+    pub fn build(&mut self, grid: &TileGrid, target: IVec2) {
+        self.target = Some(target);
+        self.costs.clear();
+        if cell_cost(grid, target).is_none() {
+            return;
+        }
+
+        let mut open = BinaryHeap::new();
+        self.costs.insert(target, 0.0);
+        open.push(OpenCell { priority: 0.0, cell: target });
+
+        while let Some(OpenCell { priority, cell }) = open.pop() {
+            if priority > self.costs.get(&cell).copied().unwrap_or(f32::MAX) {
+                continue;
+            }
+            for (next, step) in neighbors(grid, cell) {
+                let cost = priority + step;
+                if cost > self.max_cost {
+                    continue;
+                }
+                if cost < self.costs.get(&next).copied().unwrap_or(f32::MAX) {
+                    self.costs.insert(next, cost);
+                    open.push(OpenCell { priority: cost, cell: next });
+                }
+            }
+        }
+    }
+
+    pub fn cost_at(&self, cell: IVec2) -> Option<f32> {
+        self.costs.get(&cell).copied()
+    }
+
+    /// Neighbouring cell that leads downhill towards the player, if `cell` is inside the field
+    pub fn next_cell(&self, grid: &TileGrid, cell: IVec2) -> Option<IVec2> {
+        let current = self.cost_at(cell)?;
+        neighbors(grid, cell)
+            .filter_map(|(next, _)| Some((next, self.cost_at(next)?)))
+            .filter(|(_, cost)| *cost < current)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(next, _)| next)
+    }
+}
+
+fn update_flow_field(
+    grid: Res<TileGrid>,
+    mut field: ResMut<FlowField>,
+    player_q: Query<&Transform, With<Player>>,
+) {
+    let Ok(player_tf) = player_q.single() else { return };
+    let player_cell = grid.world_to_cell(player_tf.translation.truncate());
+
+    if field.target != Some(player_cell) || grid.is_changed() {
+        field.build(&grid, player_cell);
+    }
+}
+
+/// A* over the walkable tiles. Returns the cells after `start` up to and including `goal`,
+/// or None if no path is found within `max_visited` expanded cells.
+pub fn find_path(grid: &TileGrid, start: IVec2, goal: IVec2, max_visited: usize) -> Option<Vec<IVec2>> {
+    cell_cost(grid, goal)?;
+
+    // Octile distance never overestimates, since every step costs at least 1
+    let heuristic = |cell: IVec2| {
+        let d = (goal - cell).abs();
+        let (min, max) = (d.x.min(d.y) as f32, d.x.max(d.y) as f32);
+        max + (std::f32::consts::SQRT_2 - 1.0) * min
+    };
+
+    let mut open = BinaryHeap::new();
+    let mut costs: HashMap<IVec2, f32> = HashMap::new();
+    let mut came_from: HashMap<IVec2, IVec2> = HashMap::new();
+    let mut expanded: HashSet<IVec2> = HashSet::new();
+    costs.insert(start, 0.0);
+    open.push(OpenCell { priority: heuristic(start), cell: start });
+
+    let mut visited = 0;
+    while let Some(OpenCell { cell, .. }) = open.pop() {
+        if cell == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(&previous) = came_from.get(&current) {
+                if previous == start {
+                    break;
+                }
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        // A cheaper copy of this cell was already expanded, so this entry is stale
+        if !expanded.insert(cell) {
+            continue;
+        }
+        visited += 1;
+        if visited > max_visited {
+            return None;
+        }
+
+        let cost = costs[&cell];
+        for (next, step) in neighbors(grid, cell) {
+            let next_cost = cost + step;
+            if next_cost < costs.get(&next).copied().unwrap_or(f32::MAX) {
+                costs.insert(next, next_cost);
+                came_from.insert(next, cell);
+                open.push(OpenCell { priority: next_cost + heuristic(next), cell: next });
+            }
+        }
+    }
+
+    None
+}
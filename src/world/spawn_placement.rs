@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::core::camera::MainCamera;
+use crate::world::tile_grid::TileGrid;
+use crate::world::tilemap::TileType;
+
+const SEARCH_RADIUS: i32 = 12;     // Cells searched around a rejected position
+const PICKUP_ATTEMPTS: usize = 20;
+const DEFAULT_VIEW: Vec2 = Vec2::new(360.0, 640.0);
+
+/// Picks spawn positions that are on open floor inside the map: off-screen for enemies, on-screen for pickups.
+/// With no map loaded every position is accepted as-is.
+#[derive(SystemParam)]
+pub struct SpawnPlacement<'w, 's> {
+    grid: Res<'w, TileGrid>,
+    camera_q: Query<'w, 's, (&'static Camera, &'static GlobalTransform, &'static Projection), With<MainCamera>>,
+}
+
+/// Plain floor only; hazards, doors, chests and portals are never spawned on
+pub fn is_spawnable_tile(tile_type: TileType) -> bool {
+    matches!(tile_type, TileType::Floor | TileType::Grass | TileType::Stone)
+}
+
+impl SpawnPlacement<'_, '_> {
+    /// The visible world area
+    pub fn view_rect(&self) -> Rect {
+        let Ok((camera, transform, projection)) = self.camera_q.single() else {
+            return Rect::from_center_size(Vec2::ZERO, DEFAULT_VIEW);
+        };
+        let scale = match projection {
+            Projection::Orthographic(ortho) => ortho.scale,
+            _ => 1.0,
+        };
+        let size = camera.logical_viewport_size().unwrap_or(DEFAULT_VIEW) * scale;
+        Rect::from_center_size(transform.translation().truncate(), size)
+    }
+
+    pub fn is_on_screen(&self, position: Vec2) -> bool {
+        // One tile of margin so nothing pops in right at the edge
+        self.view_rect().inflate(self.grid.tile_size).contains(position)
+    }
+
+    pub fn level_center(&self) -> Option<Vec2> {
+        self.grid.center()
+    }
+
+    pub fn is_spawnable(&self, position: Vec2) -> bool {
+        self.is_cell_spawnable(self.grid.world_to_cell(position))
+    }
+
+    fn is_cell_spawnable(&self, cell: IVec2) -> bool {
+        self.grid.get(cell).is_some_and(|cell| cell.walkable && is_spawnable_tile(cell.tile_type))
+    }
+
+    /// Center of the spawnable cell closest to `position` that passes `accept`
+    pub fn nearest_spawnable(&self, position: Vec2, accept: impl Fn(Vec2) -> bool) -> Option<Vec2> {
+        let center = self.grid.world_to_cell(position);
+        for radius in 0..=SEARCH_RADIUS {
+            let ring = (-radius..=radius)
+                .flat_map(|x| (-radius..=radius).map(move |y| IVec2::new(x, y)))
+                .filter(|offset| offset.x.abs() == radius || offset.y.abs() == radius)
+                .map(|offset| center + offset)
+                .filter(|cell| self.is_cell_spawnable(*cell))
+                .map(|cell| self.grid.cell_to_world(cell))
+                .filter(|world| accept(*world))
+                .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)));
+            if ring.is_some() {
+                return ring;
+            }
+        }
+        None
+    }
+
+    /// Level spawn markers that are currently off-screen, in world space
+    pub fn off_screen_spawn_points(&self) -> Vec<Vec2> {
+        self.grid.spawn_points.iter()
+            .map(|cell| self.grid.cell_to_world(*cell))
+            .filter(|position| !self.is_on_screen(*position))
+            .collect()
+    }
+
+    /// Where to put an enemy that wants to appear at `desired`: the closest open off-screen cell,
+    /// then an off-screen level spawn marker, then the closest open cell even if visible.
+    pub fn enemy_position(&self, desired: Vec2, rng: &mut impl Rng) -> Option<Vec2> {
+        if self.grid.is_empty() {
+            return Some(desired);
+        }
+        self.nearest_spawnable(desired, |position| !self.is_on_screen(position))
+            .or_else(|| self.off_screen_spawn_points().choose(rng).copied())
+            .or_else(|| self.nearest_spawnable(desired, |_| true))
+    }
+
+    /// A random open cell on screen for a pickup, or the closest open cell to the screen's center
+    pub fn pickup_position(&self, rng: &mut impl Rng) -> Option<Vec2> {
+        let view = self.view_rect();
+        if self.grid.is_empty() {
+            return Some(view.center());
+        }
+
+        let inner = view.inflate(-self.grid.tile_size);
+        for _ in 0..PICKUP_ATTEMPTS {
+            let position = Vec2::new(
+                rng.gen_range(inner.min.x..=inner.max.x.max(inner.min.x)),
+                rng.gen_range(inner.min.y..=inner.max.y.max(inner.min.y)),
+            );
+            if self.is_spawnable(position) {
+                return Some(self.grid.cell_to_world(self.grid.world_to_cell(position)));
+            }
+        }
+        self.nearest_spawnable(view.center(), |position| view.contains(position))
+    }
+}
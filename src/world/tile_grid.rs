@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::world::tilemap::{Tile, TileType};
+
+/// Per-cell view of the spawned map, rebuilt whenever a level loads or a chunk streams in or out
+#[derive(Resource)]
+pub struct TileGrid {
+    pub tile_size: f32,
+    pub origin: Vec2, // World position of the bottom-left corner of cell (0, 0)
+    pub spawn_points: Vec<IVec2>, // Enemy spawn markers from the level file
+    cells: HashMap<IVec2, GridCell>,
+}
+
+#[derive(Clone, Copy)]
+pub struct GridCell {
+    pub tile_type: TileType,
+    pub walkable: bool,
+    pub entity: Entity,
+}
+
+impl Default for TileGrid {
+    fn default() -> Self {
+        Self {
+            tile_size: 32.0,
+            origin: Vec2::ZERO,
+            spawn_points: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl TileGrid {
+    /// Forgets every cell and realigns the grid for a new map
+    pub fn reset(&mut self, tile_size: f32, origin: Vec2) {
+        self.tile_size = tile_size;
+        self.origin = origin;
+        self.spawn_points.clear();
+        self.cells.clear();
+    }
+
+    pub fn world_to_cell(&self, position: Vec2) -> IVec2 {
+        ((position - self.origin) / self.tile_size).floor().as_ivec2()
+    }
+
+    /// World position of a cell's center
+    pub fn cell_to_world(&self, cell: IVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + Vec2::splat(0.5)) * self.tile_size
+    }
+
+    pub fn insert(&mut self, cell: IVec2, tile: Tile, entity: Entity) {
+        self.cells.insert(cell, GridCell {
+            tile_type: tile.tile_type,
+            walkable: tile.walkable,
+            entity,
+        });
+    }
+
+    pub fn remove(&mut self, cell: IVec2) {
+        self.cells.remove(&cell);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// World position at the middle of every loaded cell, or None before a map loads
+    pub fn center(&self) -> Option<Vec2> {
+        let min = self.cells.keys().copied().reduce(IVec2::min)?;
+        let max = self.cells.keys().copied().reduce(IVec2::max)?;
+        Some((self.cell_to_world(min) + self.cell_to_world(max)) / 2.0)
+    }
+
+    pub fn get(&self, cell: IVec2) -> Option<&GridCell> {
+        self.cells.get(&cell)
+    }
+
+    pub fn tile_at(&self, position: Vec2) -> Option<&GridCell> {
+        self.get(self.world_to_cell(position))
+    }
+
+    pub fn tile_type_at(&self, position: Vec2) -> Option<TileType> {
+        self.tile_at(position).map(|cell| cell.tile_type)
+    }
+
+    /// Cells without a tile (outside the map) count as walkable
+    pub fn is_cell_walkable(&self, cell: IVec2) -> bool {
+        self.get(cell).is_none_or(|cell| cell.walkable)
+    }
+
+    pub fn is_walkable(&self, position: Vec2) -> bool {
+        self.is_cell_walkable(self.world_to_cell(position))
+    }
+}
@@ -1,17 +1,25 @@
 use bevy::prelude::*;
+use serde::Deserialize;
 use crate::game::player::{Player, PlayerController};
 use crate::game::enemy::Enemy;
 use crate::game::combat::Health;
+use crate::game::combat::effects::{ApplyStatusEvent, StatusEffects, StatusKind};
+use crate::core::events::{CombatEvent, HitKind, DamageType};
 use crate::game::movement::Velocity;
+use crate::world::tile_grid::TileGrid;
+use bevy_ecs_tilemap::tiles::AnimatedTile;
 
 pub struct TilemapPlugin;
 
 impl Plugin for TilemapPlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_plugins(bevy_ecs_tilemap::TilemapPlugin)
             .init_resource::<TilemapConfig>()
+            .init_resource::<TileGrid>()
             .add_systems(Startup, crate::world::level_loader::load_level)
             .add_systems(Update, (
+                crate::world::level_loader::spawn_loaded_level,
                 apply_tile_effects,
                 apply_water_effects,
             ));
@@ -35,13 +43,6 @@ impl Default for TilemapConfig {
     }
 }
 
-#[derive(Component)]
-pub struct AnimatedTile {
-    pub frames: Vec<usize>,
-    pub current_frame: usize,
-    pub timer: Timer,
-}
-
 #[derive(Component, Clone, Copy)]
 pub struct Tile {
     pub tile_type: TileType,
@@ -49,7 +50,7 @@ pub struct Tile {
     pub tile_index: usize,
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Deserialize)]
 pub enum TileType {
     Floor,
     Wall,
@@ -90,6 +91,21 @@ impl Tile {
             tile_index,
         }
     }
+
+    /// Tileset frames for animated tiles, played by bevy_ecs_tilemap on the GPU
+    pub fn animation(&self) -> Option<AnimatedTile> {
+        let (frames, frames_per_second) = match self.tile_type {
+            TileType::Water => (4, 2.0),
+            TileType::Lava => (4, 3.3),
+            TileType::Portal => (4, 5.0),
+            _ => return None,
+        };
+        Some(AnimatedTile {
+            start: self.tile_index as u32,
+            end: (self.tile_index + frames) as u32,
+            speed: frames_per_second,
+        })
+    }
 }
 
 #[derive(Component)]
@@ -97,104 +113,89 @@ pub struct InWater {
     pub depth: f32, // 0.0 to 1.0, where 1.0 is fully submerged
 }
 
+/// Standing on spikes: hurts on contact and again every `timer` tick
 #[derive(Component)]
-pub struct OnSpikes;
+pub struct OnSpikes {
+    pub timer: Timer,
+}
+
+const PLAYER_SPIKE_DAMAGE: i32 = 15;
+const ENEMY_SPIKE_DAMAGE: i32 = 10;
+const LAVA_BURN_DURATION: f32 = 2.0;
 
-// System to handle spike damage and visual effects
+// System to handle spike damage, sent through the damage pipeline as environmental damage
 fn apply_tile_effects(
     mut commands: Commands,
-    tile_query: Query<(&Transform, &Tile), With<Tile>>,
-    mut player_query: Query<(Entity, &Transform, &mut Health), (With<Player>, Without<Enemy>)>,
-    mut enemy_query: Query<(Entity, &Transform, &mut Health), (With<Enemy>, Without<Player>)>,
-    spike_query: Query<Entity, With<OnSpikes>>,
+    grid: Res<TileGrid>,
+    mut target_query: Query<(Entity, &Transform, &Health, &StatusEffects, Option<&mut OnSpikes>, Has<Player>), Or<(With<Player>, With<Enemy>)>>,
+    mut combat_events: EventWriter<CombatEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
     time: Res<Time>,
 ) {
-    let tile_size = 32.0;
-    let half_tile = tile_size / 2.0;
-    
-    // Check players on spikes
-    for (player_entity, player_transform, mut player_health) in player_query.iter_mut() {
-        // Skip dead players
-        if player_health.is_dead() {
+    for (entity, transform, health, statuses, spikes, is_player) in target_query.iter_mut() {
+        // Skip the dead
+        if health.is_dead() {
             continue;
         }
         
-        let player_pos = player_transform.translation.truncate();
-        let mut on_spikes = false;
-        
-        for (tile_transform, tile) in tile_query.iter() {
-            if tile.tile_type == TileType::Spike {
-                let tile_pos = tile_transform.translation.truncate();
-                let distance = player_pos.distance(tile_pos);
-                
-                if distance < half_tile {
-                    on_spikes = true;
-                    // Deal damage every second when on spikes
-                    player_health.current -= (15.0 * time.delta_secs()) as i32;
-                    break;
-                }
-            }
-        }
-        
-        // Add or remove OnSpikes component safely
-        if on_spikes && !spike_query.contains(player_entity) {
-            if let Ok(mut entity_commands) = commands.get_entity(player_entity) {
-                entity_commands.insert(OnSpikes);
+        let on_spikes = grid.tile_type_at(transform.translation.truncate()) == Some(TileType::Spike);
+        let hurt = match (on_spikes, spikes) {
+            (true, Some(mut spikes)) => spikes.timer.tick(time.delta()).just_finished(),
+            (true, None) => {
+                commands.entity(entity).insert(OnSpikes {
+                    timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+                });
+                true
             }
-        } else if !on_spikes && spike_query.contains(player_entity) {
-            if let Ok(mut entity_commands) = commands.get_entity(player_entity) {
-                entity_commands.remove::<OnSpikes>();
+            (false, Some(_)) => {
+                commands.entity(entity).remove::<OnSpikes>();
+                false
             }
-        }
-    }
-    
-    // Check enemies on spikes
-    for (enemy_entity, enemy_transform, mut enemy_health) in enemy_query.iter_mut() {
-        // Skip dead enemies
-        if enemy_health.is_dead() {
-            continue;
-        }
-        
-        let enemy_pos = enemy_transform.translation.truncate();
-        let mut on_spikes = false;
+            (false, None) => false,
+        };
         
-        for (tile_transform, tile) in tile_query.iter() {
-            if tile.tile_type == TileType::Spike {
-                let tile_pos = tile_transform.translation.truncate();
-                let distance = enemy_pos.distance(tile_pos);
-                
-                if distance < half_tile {
-                    on_spikes = true;
-                    // Deal damage to enemies on spikes too
-                    enemy_health.current -= (10.0 * time.delta_secs()) as i32;
-                    break;
-                }
-            }
+        if hurt {
+            combat_events.write(CombatEvent {
+                attacker: Entity::PLACEHOLDER,
+                target: entity,
+                damage: if is_player { PLAYER_SPIKE_DAMAGE } else { ENEMY_SPIKE_DAMAGE },
+                damage_type: DamageType::Physical,
+                position: transform.translation,
+                kind: HitKind::Direct,
+            });
         }
         
-        if on_spikes && !spike_query.contains(enemy_entity) {
-            if let Ok(mut entity_commands) = commands.get_entity(enemy_entity) {
-                entity_commands.insert(OnSpikes);
-            }
-        } else if !on_spikes && spike_query.contains(enemy_entity) {
-            if let Ok(mut entity_commands) = commands.get_entity(enemy_entity) {
-                entity_commands.remove::<OnSpikes>();
-            }
+        // Lava can't be walked on, but standing at its edge sets you alight
+        if !statuses.has(StatusKind::Burn) && lava_near(&grid, transform.translation.truncate()) {
+            status_events.write(ApplyStatusEvent {
+                target: entity,
+                source: Entity::PLACEHOLDER,
+                kind: StatusKind::Burn,
+                duration: LAVA_BURN_DURATION,
+                stacks: 1,
+            });
         }
     }
 }
 
+fn lava_near(grid: &TileGrid, position: Vec2) -> bool {
+    let cell = grid.world_to_cell(position);
+    (-1..=1).flat_map(|x| (-1..=1).map(move |y| cell + IVec2::new(x, y))).any(|neighbour| {
+        grid.get(neighbour).is_some_and(|tile| tile.tile_type == TileType::Lava)
+            && position.distance(grid.cell_to_world(neighbour)) < grid.tile_size
+    })
+}
+
 // System to handle water effects (slower movement, visual depth)
 fn apply_water_effects(
     mut commands: Commands,
-    tile_query: Query<(&Transform, &Tile), With<Tile>>,
+    grid: Res<TileGrid>,
+    config: Res<TilemapConfig>,
     mut player_query: Query<(Entity, &Transform, &mut PlayerController, &mut Velocity, &Health), (With<Player>, Without<Enemy>)>,
     mut enemy_query: Query<(Entity, &Transform, &mut Velocity, &Health), (With<Enemy>, Without<Player>)>,
     water_query: Query<(Entity, &InWater)>,
 ) {
-    let tile_size = 32.0;
-    let half_tile = tile_size / 2.0;
-    let water_detection_radius = tile_size * 0.8; // Larger detection area to prevent gaps
+    let water_detection_radius = config.tile_size * 0.8; // Depth fades out towards the tile edge
     
     // Check players in water
     for (player_entity, player_transform, mut controller, mut velocity, health) in player_query.iter_mut() {
@@ -204,21 +205,7 @@ fn apply_water_effects(
         }
         
         let player_pos = player_transform.translation.truncate();
-        let mut in_water = false;
-        let mut closest_water_distance = f32::MAX;
-        
-        // Find the closest water tile
-        for (tile_transform, tile) in tile_query.iter() {
-            if tile.tile_type == TileType::Water {
-                let tile_pos = tile_transform.translation.truncate();
-                let distance = player_pos.distance(tile_pos);
-                
-                if distance < water_detection_radius {
-                    in_water = true;
-                    closest_water_distance = closest_water_distance.min(distance);
-                }
-            }
-        }
+        let (in_water, closest_water_distance) = water_under(&grid, player_pos);
         
         let mut water_depth = 0.0;
         if in_water {
@@ -260,21 +247,7 @@ fn apply_water_effects(
         }
         
         let enemy_pos = enemy_transform.translation.truncate();
-        let mut in_water = false;
-        let mut closest_water_distance = f32::MAX;
-        
-        // Find the closest water tile for enemies too
-        for (tile_transform, tile) in tile_query.iter() {
-            if tile.tile_type == TileType::Water {
-                let tile_pos = tile_transform.translation.truncate();
-                let distance = enemy_pos.distance(tile_pos);
-                
-                if distance < water_detection_radius {
-                    in_water = true;
-                    closest_water_distance = closest_water_distance.min(distance);
-                }
-            }
-        }
+        let (in_water, closest_water_distance) = water_under(&grid, enemy_pos);
         
         let mut water_depth = 0.0;
         if in_water {
@@ -302,3 +275,12 @@ fn apply_water_effects(
         }
     }
 }
+
+// Whether `position` is on a water tile, and its distance to that tile's center
+fn water_under(grid: &TileGrid, position: Vec2) -> (bool, f32) {
+    let cell = grid.world_to_cell(position);
+    match grid.get(cell) {
+        Some(tile) if tile.tile_type == TileType::Water => (true, position.distance(grid.cell_to_world(cell))),
+        _ => (false, f32::MAX),
+    }
+}